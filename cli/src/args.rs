@@ -1,4 +1,4 @@
-use clap::{arg, Parser};
+use clap::{arg, Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 pub struct NewArgs {
@@ -22,6 +22,38 @@ pub struct TestArgs {
 #[derive(Parser, Debug)]
 pub struct CleanArgs {}
 
+#[derive(Parser, Debug)]
+pub struct IdlArgs {
+    /// Path to the api crate of the program
+    #[arg(long, default_value = "./api")]
+    pub api: String,
+
+    /// File to write the IDL to. Prints to stdout if not set.
+    #[arg(long, short)]
+    pub out: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ClientArgs {
+    /// Language of the generated client
+    #[arg(long, value_enum, default_value_t = ClientLang::Ts)]
+    pub lang: ClientLang,
+
+    /// Path to the api crate of the program
+    #[arg(long, default_value = "./api")]
+    pub api: String,
+
+    /// Directory to write the client to
+    #[arg(long, short, default_value = "./client")]
+    pub out: String,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ClientLang {
+    /// TypeScript, using @solana/web3.js
+    Ts,
+}
+
 #[derive(Parser, Debug)]
 pub struct ProgramKeysArgs {
     #[command(subcommand)]
@@ -0,0 +1,26 @@
+mod ts;
+
+use std::{fs, path::Path};
+
+use crate::{idl::parse_idl, utils::to_lib_case, ClientArgs, ClientLang};
+
+pub fn client_project(args: ClientArgs) -> anyhow::Result<()> {
+    let idl = parse_idl(Path::new(&args.api))?;
+    let out = Path::new(&args.out);
+    let files = match args.lang {
+        ClientLang::Ts => ts::generate(&idl)?,
+    };
+    for (path, content) in files {
+        let path = out.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+    }
+    println!(
+        "Client for {} written to {}",
+        to_lib_case(&idl.metadata.name),
+        out.display()
+    );
+    Ok(())
+}
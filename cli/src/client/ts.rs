@@ -0,0 +1,773 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use anyhow::{anyhow, bail};
+use colored::*;
+
+use crate::{
+    idl::*,
+    utils::{to_camel_case, to_snake_case, to_type_case},
+};
+
+const CODEC_TS: &str = include_str!("../template/client_ts_codec_ts");
+const PACKAGE_JSON: &str = include_str!("../template/client_ts_package_json");
+const TSCONFIG_JSON: &str = include_str!("../template/client_ts_tsconfig_json");
+
+const HEADER: &str = "// This file was generated by `steel client`. Do not edit.\n\n";
+
+/// Size of the account header reserved by steel for the discriminator.
+const ACCOUNT_HEADER_SIZE: usize = 8;
+
+/// Maximum depth when resolving PDAs seeded by other accounts.
+const MAX_RESOLVE_DEPTH: usize = 4;
+
+/// Generates a TypeScript client package from an IDL, as a list of relative paths and contents.
+pub fn generate(idl: &Idl) -> anyhow::Result<Vec<(String, String)>> {
+    let gen = TsGenerator { idl };
+    let package_json = PACKAGE_JSON
+        .replace("{name_lowercase}", &idl.metadata.name.replace('_', "-"))
+        .replace("{version}", &idl.metadata.version);
+    Ok(vec![
+        ("package.json".to_string(), package_json),
+        ("tsconfig.json".to_string(), TSCONFIG_JSON.to_string()),
+        ("src/codec.ts".to_string(), CODEC_TS.to_string()),
+        ("src/program.ts".to_string(), gen.program()?),
+        ("src/constants.ts".to_string(), gen.constants()?),
+        ("src/types.ts".to_string(), gen.types()?),
+        ("src/accounts.ts".to_string(), gen.accounts()?),
+        ("src/pda.ts".to_string(), gen.pdas()?),
+        ("src/instructions.ts".to_string(), gen.instructions()?),
+        ("src/errors.ts".to_string(), gen.errors()?),
+        ("src/events.ts".to_string(), gen.events()?),
+        ("src/index.ts".to_string(), gen.index()?),
+    ])
+}
+
+struct TsGenerator<'a> {
+    idl: &'a Idl,
+}
+
+impl TsGenerator<'_> {
+    fn program(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(ts, "import {{ PublicKey }} from \"@solana/web3.js\";\n")?;
+        writeln!(
+            ts,
+            "export const PROGRAM_ID = new PublicKey(\"{}\");",
+            self.idl.address
+        )?;
+        Ok(ts)
+    }
+
+    fn constants(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        for constant in &self.idl.constants {
+            let value = match &constant.ty {
+                IdlType::Primitive(p) if p == "bytes" => {
+                    format!("new Uint8Array({})", constant.value)
+                }
+                ty if ts_type(ty, "") == "bigint" => format!("{}n", constant.value),
+                _ => constant.value.clone(),
+            };
+            writeln!(ts, "export const {} = {};", constant.name, value)?;
+        }
+        Ok(ts)
+    }
+
+    fn types(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(ts, "import {{ PublicKey }} from \"@solana/web3.js\";")?;
+        writeln!(ts, "import {{ Reader, Writer }} from \"./codec\";")?;
+        for def in &self.idl.types {
+            let name = &def.name;
+            let fields = &def.ty.fields;
+            writeln!(ts, "\nexport interface {} {{", name)?;
+            for field in fields {
+                writeln!(
+                    ts,
+                    "  {}: {};",
+                    to_camel_case(&field.name),
+                    ts_type(&field.ty, "")
+                )?;
+            }
+            writeln!(ts, "}}\n")?;
+            writeln!(
+                ts,
+                "export const {}_SIZE = {};\n",
+                to_constant_case(name),
+                self.fields_size(fields)?
+            )?;
+            writeln!(ts, "export function read{}(r: Reader): {} {{", name, name)?;
+            writeln!(ts, "  return {{")?;
+            for field in fields {
+                writeln!(
+                    ts,
+                    "    {}: {},",
+                    to_camel_case(&field.name),
+                    read_expr(&field.ty, "")
+                )?;
+            }
+            writeln!(ts, "  }};\n}}\n")?;
+            writeln!(
+                ts,
+                "export function write{}(w: Writer, value: {}) {{",
+                name, name
+            )?;
+            for field in fields {
+                let value = format!("value.{}", to_camel_case(&field.name));
+                writeln!(ts, "  {};", write_stmt(&field.ty, &value, ""))?;
+            }
+            writeln!(ts, "}}")?;
+        }
+        Ok(ts)
+    }
+
+    fn accounts(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(ts, "import {{ Reader, startsWith }} from \"./codec\";")?;
+        writeln!(ts, "import * as types from \"./types\";")?;
+        for account in &self.idl.accounts {
+            let name = &account.name;
+            let constant = to_constant_case(name);
+            let size = self.fields_size(&self.type_def(name)?.ty.fields)?;
+            writeln!(
+                ts,
+                "\nexport const {}_DISCRIMINATOR = new Uint8Array({:?});\n",
+                constant, account.discriminator
+            )?;
+            writeln!(
+                ts,
+                "export const {}_ACCOUNT_SIZE = {};\n",
+                constant,
+                ACCOUNT_HEADER_SIZE + size
+            )?;
+            writeln!(
+                ts,
+                "/** Returns true if the account data holds a `{}`. */",
+                name
+            )?;
+            writeln!(
+                ts,
+                "export function is{}(data: Uint8Array): boolean {{",
+                name
+            )?;
+            writeln!(
+                ts,
+                "  return startsWith(data, {}_DISCRIMINATOR);\n}}\n",
+                constant
+            )?;
+            writeln!(
+                ts,
+                "/** Decodes a `{}` account, skipping the {} byte discriminator header. */",
+                name, ACCOUNT_HEADER_SIZE
+            )?;
+            writeln!(
+                ts,
+                "export function decode{}(data: Uint8Array): types.{} {{",
+                name, name
+            )?;
+            writeln!(ts, "  if (!is{}(data)) {{", name)?;
+            writeln!(
+                ts,
+                "    throw new Error(\"Account is not of type {}\");\n  }}",
+                name
+            )?;
+            writeln!(
+                ts,
+                "  return types.read{}(new Reader(data, {}));\n}}",
+                name, ACCOUNT_HEADER_SIZE
+            )?;
+        }
+        Ok(ts)
+    }
+
+    fn pdas(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(ts, "import {{ PublicKey }} from \"@solana/web3.js\";")?;
+        writeln!(ts, "import {{ encode }} from \"./codec\";")?;
+        writeln!(ts, "import * as constants from \"./constants\";")?;
+        writeln!(ts, "import {{ PROGRAM_ID }} from \"./program\";")?;
+        writeln!(ts, "import * as types from \"./types\";")?;
+        for (name, (pda, args)) in self.collect_pdas() {
+            let mut params = vec![];
+            let mut seeds = vec![];
+            for seed in &pda.seeds {
+                match seed {
+                    IdlSeed::Const { value } => seeds.push(self.const_seed(value)),
+                    IdlSeed::Account { path } => {
+                        let param = to_camel_case(path);
+                        seeds.push(format!("{}.toBytes()", param));
+                        params.push(format!("{}: PublicKey", param));
+                    }
+                    IdlSeed::Arg { path } => {
+                        let param = to_camel_case(path);
+                        let ty = args.iter().find(|a| &a.name == path).map(|a| &a.ty);
+                        let (param_ty, seed) = match ty {
+                            Some(ty) => (ts_type(ty, "types."), self.arg_seed(ty, &param)?),
+                            None => ("Uint8Array".to_string(), param.clone()),
+                        };
+                        seeds.push(seed);
+                        params.push(format!("{}: {}", param, param_ty));
+                    }
+                }
+            }
+            params.push("programId: PublicKey = PROGRAM_ID".to_string());
+            writeln!(ts, "\n/** Derives the address of the `{}` PDA. */", name)?;
+            writeln!(
+                ts,
+                "export function find{}Pda({}): [PublicKey, number] {{",
+                to_type_case(&name),
+                params.join(", ")
+            )?;
+            writeln!(
+                ts,
+                "  return PublicKey.findProgramAddressSync([{}], programId);\n}}",
+                seeds.join(", ")
+            )?;
+        }
+        Ok(ts)
+    }
+
+    fn instructions(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(
+            ts,
+            "import {{ PublicKey, TransactionInstruction }} from \"@solana/web3.js\";"
+        )?;
+        writeln!(ts, "import {{ Writer }} from \"./codec\";")?;
+        writeln!(ts, "import * as pda from \"./pda\";")?;
+        writeln!(ts, "import {{ PROGRAM_ID }} from \"./program\";")?;
+        writeln!(ts, "import * as types from \"./types\";")?;
+        for ix in &self.idl.instructions {
+            let type_name = to_type_case(&ix.name);
+            let fn_name = to_camel_case(&ix.name);
+            let constant = ix.name.to_ascii_uppercase();
+            writeln!(
+                ts,
+                "\nexport const {}_INSTRUCTION_DISCRIMINATOR = new Uint8Array({:?});",
+                constant, ix.discriminator
+            )?;
+
+            // Accounts that can't be resolved from addresses, seeds or args are parameters.
+            let params = ix
+                .accounts
+                .iter()
+                .filter(|a| !self.is_resolvable(a, ix, 0))
+                .collect::<Vec<_>>();
+            let mut signature = vec![];
+            if !params.is_empty() {
+                writeln!(ts, "\nexport interface {}Accounts {{", type_name)?;
+                for account in &params {
+                    writeln!(ts, "  {}: PublicKey;", to_camel_case(&account.name))?;
+                }
+                writeln!(ts, "}}")?;
+                signature.push(format!("accounts: {}Accounts", type_name));
+            }
+            if !ix.args.is_empty() {
+                writeln!(ts, "\nexport interface {}Args {{", type_name)?;
+                for arg in &ix.args {
+                    writeln!(
+                        ts,
+                        "  {}: {};",
+                        to_camel_case(&arg.name),
+                        ts_type(&arg.ty, "types.")
+                    )?;
+                }
+                writeln!(ts, "}}")?;
+                signature.push(format!("args: {}Args", type_name));
+            }
+            signature.push("programId: PublicKey = PROGRAM_ID".to_string());
+
+            writeln!(
+                ts,
+                "\nexport function {}({}): TransactionInstruction {{",
+                fn_name,
+                signature.join(", ")
+            )?;
+            writeln!(
+                ts,
+                "  const w = new Writer({}_INSTRUCTION_DISCRIMINATOR.length + {});",
+                constant,
+                self.fields_size(&ix.args)?
+            )?;
+            writeln!(
+                ts,
+                "  w.bytes({}_INSTRUCTION_DISCRIMINATOR, {}_INSTRUCTION_DISCRIMINATOR.length);",
+                constant, constant
+            )?;
+            for arg in &ix.args {
+                let value = format!("args.{}", to_camel_case(&arg.name));
+                writeln!(ts, "  {};", write_stmt(&arg.ty, &value, "types."))?;
+            }
+            writeln!(ts, "  return new TransactionInstruction({{")?;
+            writeln!(ts, "    programId,")?;
+            writeln!(ts, "    keys: [")?;
+            for account in &ix.accounts {
+                writeln!(
+                    ts,
+                    "      {{ pubkey: {}, isSigner: {}, isWritable: {} }},",
+                    self.account_expr(account, ix, 0)?,
+                    account.signer,
+                    account.writable
+                )?;
+            }
+            writeln!(ts, "    ],")?;
+            writeln!(ts, "    data: Buffer.from(w.data),")?;
+            writeln!(ts, "  }});\n}}")?;
+        }
+        Ok(ts)
+    }
+
+    fn errors(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(ts, "export enum ErrorCode {{")?;
+        for error in &self.idl.errors {
+            writeln!(ts, "  {} = {},", error.name, error.code)?;
+        }
+        writeln!(ts, "}}\n")?;
+        writeln!(ts, "export interface ProgramError {{")?;
+        writeln!(ts, "  code: number;\n  name: string;\n  msg: string;\n}}\n")?;
+        writeln!(ts, "export const ERRORS: ProgramError[] = [")?;
+        for error in &self.idl.errors {
+            writeln!(
+                ts,
+                "  {{ code: {}, name: {}, msg: {} }},",
+                error.code,
+                serde_json::to_string(&error.name)?,
+                serde_json::to_string(error.msg.as_ref().unwrap_or(&error.name))?
+            )?;
+        }
+        writeln!(ts, "];\n")?;
+        writeln!(
+            ts,
+            "/** Returns the program error for a custom error code. */"
+        )?;
+        writeln!(
+            ts,
+            "export function errorFromCode(code: number): ProgramError | undefined {{"
+        )?;
+        writeln!(ts, "  return ERRORS.find((e) => e.code === code);\n}}")?;
+        Ok(ts)
+    }
+
+    fn events(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        writeln!(ts, "import {{ PublicKey }} from \"@solana/web3.js\";")?;
+        writeln!(ts, "import {{ Reader }} from \"./codec\";")?;
+        writeln!(ts, "import {{ PROGRAM_ID }} from \"./program\";")?;
+        writeln!(ts, "import * as types from \"./types\";\n")?;
+
+        let variants = self
+            .idl
+            .events
+            .iter()
+            .map(|e| format!("\n  | {{ name: \"{}\"; data: types.{} }}", e.name, e.name))
+            .collect::<String>();
+        if variants.is_empty() {
+            writeln!(ts, "export type ProgramEvent = never;\n")?;
+        } else {
+            writeln!(ts, "export type ProgramEvent ={};\n", variants)?;
+        }
+
+        // Events have no discriminator, so they are matched by size.
+        writeln!(ts, "/**")?;
+        writeln!(ts, " * Decodes the payload of a `Program data:` log.")?;
+        writeln!(ts, " *")?;
+        writeln!(
+            ts,
+            " * Events have no discriminator, so they are matched by their size."
+        )?;
+        writeln!(ts, " */")?;
+        writeln!(
+            ts,
+            "export function decodeEvent(data: Uint8Array): ProgramEvent | undefined {{"
+        )?;
+        let mut sizes = BTreeMap::new();
+        for event in &self.idl.events {
+            let size = self.fields_size(&self.type_def(&event.name)?.ty.fields)?;
+            if let Some(other) = sizes.insert(size, &event.name) {
+                eprintln!(
+                    "{}: events {} and {} have the same size and can't be told apart",
+                    "WARNING".bold().yellow(),
+                    other,
+                    event.name
+                );
+                sizes.insert(size, other);
+                continue;
+            }
+            writeln!(
+                ts,
+                "  if (data.length === types.{}_SIZE) {{",
+                to_constant_case(&event.name)
+            )?;
+            writeln!(
+                ts,
+                "    return {{ name: \"{}\", data: types.read{}(new Reader(data)) }};\n  }}",
+                event.name, event.name
+            )?;
+        }
+        writeln!(ts, "  return undefined;\n}}\n")?;
+
+        writeln!(
+            ts,
+            "/** Parses the events emitted by the program from transaction logs. */"
+        )?;
+        writeln!(
+            ts,
+            "export function parseEvents(logs: string[], programId: PublicKey = PROGRAM_ID): ProgramEvent[] {{"
+        )?;
+        writeln!(ts, "  const id = programId.toBase58();")?;
+        writeln!(ts, "  const stack: string[] = [];")?;
+        writeln!(ts, "  const events: ProgramEvent[] = [];")?;
+        writeln!(ts, "  for (const log of logs) {{")?;
+        writeln!(
+            ts,
+            "    const invoke = log.match(/^Program (\\w+) invoke \\[\\d+\\]$/);"
+        )?;
+        writeln!(ts, "    if (invoke) {{")?;
+        writeln!(ts, "      stack.push(invoke[1]);")?;
+        writeln!(
+            ts,
+            "    }} else if (/^Program \\w+ (success|failed)/.test(log)) {{"
+        )?;
+        writeln!(ts, "      stack.pop();")?;
+        writeln!(
+            ts,
+            "    }} else if (log.startsWith(\"Program data: \") && stack[stack.length - 1] === id) {{"
+        )?;
+        writeln!(
+            ts,
+            "      const data = Buffer.from(log.slice(\"Program data: \".length).split(\" \")[0], \"base64\");"
+        )?;
+        writeln!(ts, "      const event = decodeEvent(new Uint8Array(data));")?;
+        writeln!(
+            ts,
+            "      if (event) {{\n        events.push(event);\n      }}"
+        )?;
+        writeln!(ts, "    }}\n  }}\n  return events;\n}}")?;
+        Ok(ts)
+    }
+
+    fn index(&self) -> anyhow::Result<String> {
+        let mut ts = HEADER.to_string();
+        for module in [
+            "accounts",
+            "codec",
+            "constants",
+            "errors",
+            "events",
+            "instructions",
+            "pda",
+            "program",
+            "types",
+        ] {
+            writeln!(ts, "export * from \"./{}\";", module)?;
+        }
+        Ok(ts)
+    }
+
+    fn type_def(&self, name: &str) -> anyhow::Result<&IdlTypeDef> {
+        self.idl
+            .types
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| anyhow!("type {} not found in IDL", name))
+    }
+
+    fn fields_size(&self, fields: &[IdlField]) -> anyhow::Result<usize> {
+        fields.iter().map(|f| self.type_size(&f.ty)).sum()
+    }
+
+    fn type_size(&self, ty: &IdlType) -> anyhow::Result<usize> {
+        Ok(match ty {
+            IdlType::Primitive(p) => match p.as_str() {
+                "u8" | "i8" | "bool" => 1,
+                "u16" | "i16" => 2,
+                "u32" | "i32" => 4,
+                "u64" | "i64" => 8,
+                "u128" | "i128" => 16,
+                "pubkey" => 32,
+                _ => bail!("type {} has no fixed size", p),
+            },
+            IdlType::Array { array } => self.type_size(&array.0)? * array.1,
+            IdlType::Defined { defined } => {
+                self.fields_size(&self.type_def(&defined.name)?.ty.fields)?
+            }
+        })
+    }
+
+    /// Collects the PDAs used by instructions, keyed by account name, along with the args of
+    /// the first instruction using them.
+    fn collect_pdas(&self) -> BTreeMap<String, (&IdlPda, &[IdlField])> {
+        let mut pdas = BTreeMap::new();
+        for ix in &self.idl.instructions {
+            for account in &ix.accounts {
+                if let Some(pda) = &account.pda {
+                    pdas.entry(account.name.clone())
+                        .or_insert((pda, ix.args.as_slice()));
+                }
+            }
+        }
+        pdas
+    }
+
+    /// Returns a seed expression for a const seed, preferring a named constant.
+    fn const_seed(&self, value: &[u8]) -> String {
+        let bytes = format!("{:?}", value);
+        match self
+            .idl
+            .constants
+            .iter()
+            .find(|c| c.ty == IdlType::Primitive("bytes".to_string()) && c.value == bytes)
+        {
+            Some(constant) => format!("constants.{}", constant.name),
+            None => format!("new Uint8Array({})", bytes),
+        }
+    }
+
+    /// Returns a seed expression encoding an instruction arg as bytes.
+    fn arg_seed(&self, ty: &IdlType, param: &str) -> anyhow::Result<String> {
+        Ok(match ty {
+            IdlType::Primitive(p) if p == "pubkey" => format!("{}.toBytes()", param),
+            IdlType::Array { array } if is_u8(&array.0) => param.to_string(),
+            ty => format!(
+                "encode({}, (w) => {})",
+                self.type_size(ty)?,
+                write_stmt(ty, param, "types.")
+            ),
+        })
+    }
+
+    /// Returns true if an account can be derived without being passed in by the caller.
+    fn is_resolvable(
+        &self,
+        account: &IdlInstructionAccount,
+        ix: &IdlInstruction,
+        depth: usize,
+    ) -> bool {
+        if account.address.is_some() {
+            return true;
+        }
+        let Some(pda) = &account.pda else {
+            return false;
+        };
+        if depth > MAX_RESOLVE_DEPTH {
+            return false;
+        }
+        pda.seeds.iter().all(|seed| match seed {
+            IdlSeed::Const { .. } => true,
+            IdlSeed::Arg { path } => ix.args.iter().any(|a| &a.name == path),
+            IdlSeed::Account { path } => {
+                // Seeding accounts passed in by the caller are always available.
+                match ix.accounts.iter().find(|a| &a.name == path) {
+                    Some(other) => {
+                        other.address.is_some()
+                            || other.pda.is_none()
+                            || self.is_resolvable(other, ix, depth + 1)
+                    }
+                    None => false,
+                }
+            }
+        })
+    }
+
+    /// Returns the expression of an account key in an instruction builder.
+    fn account_expr(
+        &self,
+        account: &IdlInstructionAccount,
+        ix: &IdlInstruction,
+        depth: usize,
+    ) -> anyhow::Result<String> {
+        if !self.is_resolvable(account, ix, depth) {
+            return Ok(format!("accounts.{}", to_camel_case(&account.name)));
+        }
+        if let Some(address) = &account.address {
+            return Ok(if *address == self.idl.address {
+                "programId".to_string()
+            } else {
+                format!("new PublicKey(\"{}\")", address)
+            });
+        }
+        let pda = account.pda.as_ref().unwrap();
+        let mut args = vec![];
+        for seed in &pda.seeds {
+            match seed {
+                IdlSeed::Const { .. } => {}
+                IdlSeed::Arg { path } => args.push(format!("args.{}", to_camel_case(path))),
+                IdlSeed::Account { path } => {
+                    let other = ix.accounts.iter().find(|a| &a.name == path).unwrap();
+                    args.push(self.account_expr(other, ix, depth + 1)?);
+                }
+            }
+        }
+        args.push("programId".to_string());
+        Ok(format!(
+            "pda.find{}Pda({})[0]",
+            to_type_case(&account.name),
+            args.join(", ")
+        ))
+    }
+}
+
+fn is_u8(ty: &IdlType) -> bool {
+    *ty == IdlType::Primitive("u8".to_string())
+}
+
+fn to_constant_case(name: &str) -> String {
+    to_snake_case(name).to_ascii_uppercase()
+}
+
+/// Returns the TypeScript type of an IDL type. Defined types are prefixed with `prefix`.
+fn ts_type(ty: &IdlType, prefix: &str) -> String {
+    match ty {
+        IdlType::Primitive(p) => match p.as_str() {
+            "u8" | "u16" | "u32" | "i8" | "i16" | "i32" => "number",
+            "u64" | "u128" | "i64" | "i128" => "bigint",
+            "bool" => "boolean",
+            "pubkey" => "PublicKey",
+            _ => "Uint8Array",
+        }
+        .to_string(),
+        IdlType::Array { array } if is_u8(&array.0) => "Uint8Array".to_string(),
+        IdlType::Array { array } => format!("{}[]", ts_type(&array.0, prefix)),
+        IdlType::Defined { defined } => format!("{}{}", prefix, defined.name),
+    }
+}
+
+/// Returns an expression reading an IDL type from a `Reader` named `r`.
+fn read_expr(ty: &IdlType, prefix: &str) -> String {
+    match ty {
+        IdlType::Primitive(p) => format!("r.{}()", p),
+        IdlType::Array { array } if is_u8(&array.0) => format!("r.bytes({})", array.1),
+        IdlType::Array { array } => format!(
+            "Array.from({{ length: {} }}, () => {})",
+            array.1,
+            read_expr(&array.0, prefix)
+        ),
+        IdlType::Defined { defined } => format!("{}read{}(r)", prefix, defined.name),
+    }
+}
+
+/// Returns a statement writing a value of an IDL type to a `Writer` named `w`.
+fn write_stmt(ty: &IdlType, value: &str, prefix: &str) -> String {
+    match ty {
+        IdlType::Primitive(p) => format!("w.{}({})", p, value),
+        IdlType::Array { array } if is_u8(&array.0) => format!("w.bytes({}, {})", value, array.1),
+        IdlType::Array { array } => format!(
+            "{}.forEach((x) => {})",
+            value,
+            write_stmt(&array.0, "x", prefix)
+        ),
+        IdlType::Defined { defined } => format!("{}write{}(w, {})", prefix, defined.name, value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counter_idl() -> Idl {
+        let u64_field = |name: &str| IdlField {
+            name: name.to_string(),
+            ty: IdlType::Primitive("u64".to_string()),
+        };
+        Idl {
+            address: "z7msBPQHDJjTvdQRoEcKyENgXDhSRYeHieN1ZMTqo35".to_string(),
+            metadata: IdlMetadata {
+                name: "counter_api".to_string(),
+                version: "0.1.0".to_string(),
+                spec: IDL_SPEC.to_string(),
+            },
+            instructions: vec![IdlInstruction {
+                name: "add".to_string(),
+                discriminator: vec![1],
+                accounts: vec![
+                    IdlInstructionAccount {
+                        name: "signer".to_string(),
+                        writable: true,
+                        signer: true,
+                        ..Default::default()
+                    },
+                    IdlInstructionAccount {
+                        name: "counter".to_string(),
+                        writable: true,
+                        pda: Some(IdlPda {
+                            seeds: vec![
+                                IdlSeed::Const {
+                                    value: b"counter".to_vec(),
+                                },
+                                IdlSeed::Account {
+                                    path: "signer".to_string(),
+                                },
+                            ],
+                        }),
+                        ..Default::default()
+                    },
+                ],
+                args: vec![u64_field("amount")],
+            }],
+            accounts: vec![IdlAccount {
+                name: "Counter".to_string(),
+                discriminator: vec![0; 8],
+            }],
+            events: vec![IdlEvent {
+                name: "AddEvent".to_string(),
+                discriminator: vec![],
+            }],
+            errors: vec![IdlError {
+                code: 0,
+                name: "Dummy".to_string(),
+                msg: Some("This is a \"dummy\" error".to_string()),
+            }],
+            types: vec![
+                IdlTypeDef {
+                    name: "AddEvent".to_string(),
+                    ty: IdlTypeDefTy {
+                        kind: "struct".to_string(),
+                        fields: vec![u64_field("amount"), u64_field("total")],
+                    },
+                },
+                IdlTypeDef {
+                    name: "Counter".to_string(),
+                    ty: IdlTypeDefTy {
+                        kind: "struct".to_string(),
+                        fields: vec![u64_field("value")],
+                    },
+                },
+            ],
+            constants: vec![IdlConst {
+                name: "COUNTER".to_string(),
+                ty: IdlType::Primitive("bytes".to_string()),
+                value: format!("{:?}", b"counter"),
+            }],
+        }
+    }
+
+    #[test]
+    fn generate_ts_client() {
+        let files = generate(&counter_idl()).unwrap();
+        let file = |name: &str| &files.iter().find(|(path, _)| path == name).unwrap().1;
+
+        let accounts = file("src/accounts.ts");
+        assert!(accounts.contains("export const COUNTER_ACCOUNT_SIZE = 16;"));
+        assert!(accounts.contains("return types.readCounter(new Reader(data, 8));"));
+
+        let pda = file("src/pda.ts");
+        assert!(pda.contains(
+            "export function findCounterPda(signer: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number]"
+        ));
+        assert!(pda.contains("[constants.COUNTER, signer.toBytes()]"));
+
+        let instructions = file("src/instructions.ts");
+        assert!(instructions.contains("export interface AddAccounts {\n  signer: PublicKey;\n}"));
+        assert!(instructions.contains(
+            "{ pubkey: pda.findCounterPda(accounts.signer, programId)[0], isSigner: false, isWritable: true }"
+        ));
+        assert!(instructions.contains("w.u64(args.amount);"));
+
+        let errors = file("src/errors.ts");
+        assert!(errors.contains(r#"{ code: 0, name: "Dummy", msg: "This is a \"dummy\" error" }"#));
+
+        let events = file("src/events.ts");
+        assert!(events.contains("if (data.length === types.ADD_EVENT_SIZE)"));
+    }
+}
@@ -0,0 +1,22 @@
+mod model;
+mod parse;
+
+use std::{fs, path::Path};
+
+pub use model::*;
+pub use parse::parse_idl;
+
+use crate::IdlArgs;
+
+pub fn idl_project(args: IdlArgs) -> anyhow::Result<()> {
+    let idl = parse_idl(Path::new(&args.api))?;
+    let json = serde_json::to_string_pretty(&idl)?;
+    match args.out {
+        Some(out) => {
+            fs::write(&out, json)?;
+            println!("IDL written to {}", out);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use serde::Serialize;
+
+/// The IDL spec version emitted by `steel idl`, matching the Anchor IDL format.
+pub const IDL_SPEC: &str = "0.1.0";
+
+/// A machine-readable description of a steel program.
+///
+/// The layout follows the Anchor IDL spec so Anchor and Codama tooling can consume it. Steel
+/// accounts reserve an 8 byte header. Account discriminators of up to 4 bytes are zero-padded and
+/// followed by the account version, since the rest of the header holds per-account data such as
+/// the PDA bump. Wider account discriminators are zero-padded to 8 bytes.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Idl {
+    pub address: String,
+    pub metadata: IdlMetadata,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlAccount>,
+    pub events: Vec<IdlEvent>,
+    pub errors: Vec<IdlError>,
+    pub types: Vec<IdlTypeDef>,
+    pub constants: Vec<IdlConst>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IdlMetadata {
+    pub name: String,
+    pub version: String,
+    pub spec: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<IdlInstructionAccount>,
+    pub args: Vec<IdlField>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IdlInstructionAccount {
+    pub name: String,
+    #[serde(skip_serializing_if = "is_false")]
+    pub writable: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub signer: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pda: Option<IdlPda>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlPda {
+    pub seeds: Vec<IdlSeed>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlSeed {
+    Const { value: Vec<u8> },
+    Arg { path: String },
+    Account { path: String },
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlAccount {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlEvent {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlError {
+    pub code: u32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlTypeDefTy {
+    pub kind: String,
+    pub fields: Vec<IdlField>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum IdlType {
+    Primitive(String),
+    Array { array: (Box<IdlType>, usize) },
+    Defined { defined: IdlDefined },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IdlDefined {
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdlConst {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+    pub value: String,
+}
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
@@ -0,0 +1,1311 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+use sha2::{Digest, Sha256};
+use syn::{
+    punctuated::Punctuated, Attribute, Expr, Fields, FnArg, Ident, Item, ItemConst, ItemEnum,
+    ItemFn, ItemStruct, Lit, Macro, Member, Pat, ReturnType, Stmt, Token, Type,
+};
+use toml::Value;
+
+use super::model::*;
+use crate::utils::{to_lib_case, to_snake_case};
+
+/// Size of the account header reserved by steel for the discriminator.
+const ACCOUNT_HEADER_SIZE: usize = 8;
+
+/// Offset of the version byte in the account header.
+const ACCOUNT_VERSION_OFFSET: usize = 4;
+
+/// Well known programs and sysvars, keyed by the module their `ID` is declared in.
+const KNOWN_ADDRESSES: &[(&str, &str, &str)] = &[
+    (
+        "system_program",
+        "system_program",
+        "11111111111111111111111111111111",
+    ),
+    (
+        "spl_token",
+        "token_program",
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    ),
+    (
+        "spl_token_2022",
+        "token_program",
+        "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    ),
+    (
+        "spl_associated_token_account",
+        "associated_token_program",
+        "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+    ),
+    (
+        "rent",
+        "rent",
+        "SysvarRent111111111111111111111111111111111",
+    ),
+    (
+        "clock",
+        "clock",
+        "SysvarC1ock11111111111111111111111111111111",
+    ),
+    (
+        "slot_hashes",
+        "slot_hashes",
+        "SysvarS1otHashes111111111111111111111111111",
+    ),
+    (
+        "instructions",
+        "instructions_sysvar",
+        "Sysvar1nstructions1111111111111111111111111",
+    ),
+];
+
+const PRIMITIVES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "bool",
+];
+
+/// Parses the api crate at the given path into an IDL.
+pub fn parse_idl(api_path: &Path) -> anyhow::Result<Idl> {
+    let mut sources = vec![];
+    collect_sources(&api_path.join("src"), &mut sources)?;
+    let mut idl = parse_sources(&sources)?;
+    let (name, version) = read_package(api_path)?;
+    idl.metadata = IdlMetadata {
+        name,
+        version,
+        spec: IDL_SPEC.to_string(),
+    };
+    Ok(idl)
+}
+
+/// Parses the source files of an api crate into an IDL, without package metadata.
+pub fn parse_sources(sources: &[(PathBuf, String)]) -> anyhow::Result<Idl> {
+    let mut api = ApiItems::default();
+    for (path, source) in sources {
+        let file = syn::parse_file(source)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        api.collect(file.items);
+    }
+    api.into_idl()
+}
+
+/// Recursively reads all Rust source files in a directory.
+fn collect_sources(dir: &Path, sources: &mut Vec<(PathBuf, String)>) -> anyhow::Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_sources(&path, sources)?;
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            sources.push((path.clone(), fs::read_to_string(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the package name and version from the api crate manifest.
+fn read_package(api_path: &Path) -> anyhow::Result<(String, String)> {
+    let manifest: Value = fs::read_to_string(api_path.join("Cargo.toml"))?.parse()?;
+    let package = manifest
+        .get("package")
+        .ok_or_else(|| anyhow!("api Cargo.toml is missing [package]"))?;
+    let name = package
+        .get("name")
+        .and_then(|n| n.as_str())
+        .ok_or_else(|| anyhow!("api Cargo.toml is missing a package name"))?;
+    let version = match package.get("version") {
+        Some(Value::String(version)) => version.clone(),
+        Some(Value::Table(_)) => {
+            let workspace_path = api_path.join("..").join("Cargo.toml");
+            let workspace: Value = fs::read_to_string(workspace_path)?.parse()?;
+            workspace
+                .get("workspace")
+                .and_then(|w| w.get("package"))
+                .and_then(|p| p.get("version"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("0.0.0")
+                .to_string()
+        }
+        _ => "0.0.0".to_string(),
+    };
+    Ok((to_lib_case(name), version))
+}
+
+/// How the discriminator of an account or instruction is declared.
+enum DiscriminatorSpec {
+    Value { expr: Expr, width: Option<usize> },
+    Hash { preimage: String },
+}
+
+/// Items collected from the api crate.
+#[derive(Default)]
+struct ApiItems {
+    address: Option<String>,
+    structs: Vec<ItemStruct>,
+    enums: Vec<ItemEnum>,
+    consts: Vec<ItemConst>,
+    fns: Vec<ItemFn>,
+    accounts: Vec<(String, DiscriminatorSpec, u8)>,
+    instructions: Vec<(String, DiscriminatorSpec)>,
+    pdas: Vec<(String, Vec<Expr>)>,
+    events: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl ApiItems {
+    fn collect(&mut self, items: Vec<Item>) {
+        for item in items {
+            match item {
+                Item::Struct(item) => {
+                    self.collect_derives(&item);
+                    self.structs.push(item);
+                }
+                Item::Enum(item) => self.enums.push(item),
+                Item::Const(item) => self.consts.push(item),
+                Item::Fn(item) => self.fns.push(item),
+                Item::Macro(item) => self.collect_macro(&item.mac),
+                Item::Mod(item) => {
+                    if let Some((_, items)) = item.content {
+                        self.collect(items);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Collects `account!`, `instruction!`, `event!`, `error!` and `declare_id!` invocations.
+    fn collect_macro(&mut self, mac: &Macro) {
+        let Some(name) = mac.path.segments.last().map(|s| s.ident.to_string()) else {
+            return;
+        };
+        if name == "declare_id" {
+            if let Ok(lit) = mac.parse_body::<syn::LitStr>() {
+                self.address = Some(lit.value());
+            }
+            return;
+        }
+        let Ok(args) = mac.parse_body_with(Punctuated::<Ident, Token![,]>::parse_terminated) else {
+            return;
+        };
+        let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        match (name.as_str(), args.as_slice()) {
+            ("account", [e, s]) => self.accounts.push((
+                s.clone(),
+                DiscriminatorSpec::Value {
+                    expr: syn::parse_str(&format!("{}::{}", e, s)).unwrap(),
+                    width: Some(1),
+                },
+                0,
+            )),
+            ("instruction", [e, s]) => self.instructions.push((
+                s.clone(),
+                DiscriminatorSpec::Value {
+                    expr: syn::parse_str(&format!("{}::{}", e, s)).unwrap(),
+                    width: None,
+                },
+            )),
+            ("event", [s]) => self.events.push(s.clone()),
+            ("error", [e]) => self.errors.push(e.clone()),
+            _ => {}
+        }
+    }
+
+    /// Collects structs using the `Account`, `Instruction`, `Pda` and `Event` derive macros.
+    fn collect_derives(&mut self, item: &ItemStruct) {
+        let name = item.ident.to_string();
+        for derive in derives(&item.attrs) {
+            match derive.as_str() {
+                "Account" => {
+                    if let Some(spec) = discriminator_spec(&item.attrs, "account", "account", &name)
+                    {
+                        let version = account_version(&item.attrs);
+                        self.accounts.push((name.clone(), spec, version));
+                    }
+                }
+                "Instruction" => {
+                    if let Some(spec) = discriminator_spec(
+                        &item.attrs,
+                        "instruction",
+                        "global",
+                        &to_snake_case(&name),
+                    ) {
+                        self.instructions.push((name.clone(), spec));
+                    }
+                }
+                "Pda" => {
+                    if let Some(seeds) = pda_seeds(&item.attrs) {
+                        self.pdas.push((name.clone(), seeds));
+                    }
+                }
+                "Event" => self.events.push(name.clone()),
+                _ => {}
+            }
+        }
+    }
+
+    fn into_idl(self) -> anyhow::Result<Idl> {
+        let mut idl = Idl {
+            address: self.address.clone().unwrap_or_default(),
+            ..Default::default()
+        };
+        let mut defined = BTreeSet::new();
+
+        // Accounts
+        for (name, spec, version) in &self.accounts {
+            let mut discriminator = self.resolve_discriminator(spec)?;
+            if discriminator.len() > ACCOUNT_HEADER_SIZE {
+                bail!("account {} discriminator is wider than the header", name);
+            }
+            if discriminator.len() <= ACCOUNT_VERSION_OFFSET {
+                // The rest of the header holds the bump and flags, which vary per account.
+                discriminator.resize(ACCOUNT_VERSION_OFFSET, 0);
+                discriminator.push(*version);
+            } else if *version != 0 {
+                bail!(
+                    "account {} is versioned but its discriminator is too wide",
+                    name
+                );
+            } else {
+                discriminator.resize(ACCOUNT_HEADER_SIZE, 0);
+            }
+            idl.accounts.push(IdlAccount {
+                name: name.clone(),
+                discriminator,
+            });
+            defined.insert(name.clone());
+        }
+
+        // Instructions
+        let sdk = self.sdk_accounts()?;
+        for (name, spec) in &self.instructions {
+            let args = self.struct_fields(name)?;
+            let mut accounts = sdk.get(name).cloned().unwrap_or_default();
+            classify_seeds(&mut accounts, &args);
+            collect_defined(&args, &mut defined);
+            idl.instructions.push(IdlInstruction {
+                name: to_snake_case(name),
+                discriminator: self.resolve_discriminator(spec)?,
+                accounts,
+                args,
+            });
+        }
+
+        // Events
+        for name in &self.events {
+            idl.events.push(IdlEvent {
+                name: name.clone(),
+                discriminator: vec![],
+            });
+            defined.insert(name.clone());
+        }
+
+        // Errors
+        for name in &self.errors {
+            let item = self
+                .find_enum(name)
+                .ok_or_else(|| anyhow!("error enum {} not found", name))?;
+            for (variant, code) in item.variants.iter().zip(self.enum_values(item)?) {
+                idl.errors.push(IdlError {
+                    code: code as u32,
+                    name: variant.ident.to_string(),
+                    msg: error_message(&variant.attrs),
+                });
+            }
+        }
+
+        // Types, including any types referenced by their fields.
+        let mut pending = defined.into_iter().collect::<Vec<_>>();
+        let mut seen = BTreeSet::new();
+        while let Some(name) = pending.pop() {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let fields = match self.find_struct(&name) {
+                Some(_) => self.struct_fields(&name)?,
+                None if name == "Numeric" => vec![IdlField {
+                    name: "bits".to_string(),
+                    ty: IdlType::Array {
+                        array: (Box::new(IdlType::Primitive("u8".to_string())), 16),
+                    },
+                }],
+                None => bail!("type {} not found", name),
+            };
+            let mut nested = BTreeSet::new();
+            collect_defined(&fields, &mut nested);
+            pending.extend(nested);
+            idl.types.push(IdlTypeDef {
+                name,
+                ty: IdlTypeDefTy {
+                    kind: "struct".to_string(),
+                    fields,
+                },
+            });
+        }
+        idl.types.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Constants
+        for item in &self.consts {
+            if let Some(bytes) = self.const_bytes(&item.expr) {
+                idl.constants.push(IdlConst {
+                    name: item.ident.to_string(),
+                    ty: IdlType::Primitive("bytes".to_string()),
+                    value: format!("{:?}", bytes),
+                });
+            } else if let Some(value) = self.eval_int(&item.expr) {
+                idl.constants.push(IdlConst {
+                    name: item.ident.to_string(),
+                    ty: self.map_type(&item.ty)?,
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        Ok(idl)
+    }
+
+    fn find_struct(&self, name: &str) -> Option<&ItemStruct> {
+        self.structs.iter().find(|s| s.ident == name)
+    }
+
+    fn find_enum(&self, name: &str) -> Option<&ItemEnum> {
+        self.enums.iter().find(|e| e.ident == name)
+    }
+
+    fn find_fn(&self, name: &str) -> Option<&ItemFn> {
+        self.fns.iter().find(|f| f.sig.ident == name)
+    }
+
+    fn struct_fields(&self, name: &str) -> anyhow::Result<Vec<IdlField>> {
+        let item = self
+            .find_struct(name)
+            .ok_or_else(|| anyhow!("struct {} not found", name))?;
+        match &item.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|f| {
+                    Ok(IdlField {
+                        name: f.ident.as_ref().unwrap().to_string(),
+                        ty: self
+                            .map_type(&f.ty)
+                            .with_context(|| format!("in {}", name))?,
+                    })
+                })
+                .collect(),
+            Fields::Unit => Ok(vec![]),
+            Fields::Unnamed(_) => bail!("tuple struct {} is not supported", name),
+        }
+    }
+
+    /// Maps a Rust field type to its IDL type.
+    fn map_type(&self, ty: &Type) -> anyhow::Result<IdlType> {
+        match ty {
+            Type::Path(path) => {
+                let Some(segment) = path.path.segments.last() else {
+                    bail!("unsupported type {}", quote::quote!(#ty));
+                };
+                let ident = segment.ident.to_string();
+                Ok(match ident.as_str() {
+                    p if PRIMITIVES.contains(&p) => IdlType::Primitive(ident),
+                    "Pubkey" => IdlType::Primitive("pubkey".to_string()),
+                    "PodBool" => IdlType::Primitive("bool".to_string()),
+                    "PodPubkey" => IdlType::Primitive("pubkey".to_string()),
+                    // Little-endian integer wrappers have the same layout as their integer.
+                    "PodU16" | "PodU32" | "PodU64" | "PodU128" | "PodI64" => {
+                        IdlType::Primitive(ident[3..].to_ascii_lowercase())
+                    }
+                    // Fixed-point wrappers are stored as little-endian bytes, like `Numeric`.
+                    p if p.starts_with("Pod") && fixed_point_size(&p[3..]).is_some() => {
+                        bytes_type(fixed_point_size(&p[3..]).unwrap())
+                    }
+                    "PodFixed" => bytes_type(
+                        fixed_generic(segment)
+                            .and_then(|ident| fixed_point_size(&ident))
+                            .ok_or_else(|| anyhow!("unsupported PodFixed precision"))?,
+                    ),
+                    // Null-padded strings and bitmaps are stored as plain byte arrays.
+                    "PodString" | "PodBitmap" => IdlType::Array {
+                        array: (
+                            Box::new(IdlType::Primitive("u8".to_string())),
+                            self.const_generic(segment)
+                                .ok_or_else(|| anyhow!("unsupported {} length", ident))?
+                                as usize,
+                        ),
+                    },
+                    _ => IdlType::Defined {
+                        defined: IdlDefined { name: ident },
+                    },
+                })
+            }
+            Type::Array(array) => {
+                let len = self
+                    .eval_int(&array.len)
+                    .ok_or_else(|| anyhow!("unsupported array length"))?;
+                Ok(IdlType::Array {
+                    array: (Box::new(self.map_type(&array.elem)?), len as usize),
+                })
+            }
+            Type::Reference(reference) => self.map_type(&reference.elem),
+            Type::Slice(slice)
+                if self.map_type(&slice.elem)? == IdlType::Primitive("u8".into()) =>
+            {
+                Ok(IdlType::Primitive("bytes".to_string()))
+            }
+            _ => bail!("unsupported type {}", quote::quote!(#ty)),
+        }
+    }
+
+    /// Evaluates the first const generic argument of a type, e.g. `32` in `PodString<32>`.
+    fn const_generic(&self, segment: &syn::PathSegment) -> Option<u128> {
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        match args.args.first()? {
+            syn::GenericArgument::Const(expr) => self.eval_int(expr),
+            syn::GenericArgument::Type(Type::Path(path)) => {
+                self.eval_int(&syn::parse_quote!(#path))
+            }
+            _ => None,
+        }
+    }
+
+    /// Evaluates an integer literal, constant or enum variant.
+    fn eval_int(&self, expr: &Expr) -> Option<u128> {
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Int(int) => int.base10_parse::<u128>().ok(),
+                _ => None,
+            },
+            Expr::Paren(paren) => self.eval_int(&paren.expr),
+            Expr::Cast(cast) => self.eval_int(&cast.expr),
+            Expr::Group(group) => self.eval_int(&group.expr),
+            Expr::Path(path) => {
+                let segments = path.path.segments.iter().collect::<Vec<_>>();
+                match segments.as_slice() {
+                    [.., e, v] => {
+                        let item = self.find_enum(&e.ident.to_string())?;
+                        let index = item.variants.iter().position(|x| x.ident == v.ident)?;
+                        self.enum_values(item).ok()?.get(index).copied()
+                    }
+                    [c] => {
+                        let item = self.consts.iter().find(|x| x.ident == c.ident)?;
+                        self.eval_int(&item.expr)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value of each enum variant, following Rust's implicit numbering.
+    fn enum_values(&self, item: &ItemEnum) -> anyhow::Result<Vec<u128>> {
+        let mut next = 0;
+        let mut values = vec![];
+        for variant in &item.variants {
+            let value = match &variant.discriminant {
+                Some((_, expr)) => self
+                    .eval_int(expr)
+                    .ok_or_else(|| anyhow!("unsupported discriminant in {}", item.ident))?,
+                None => next,
+            };
+            values.push(value);
+            next = value + 1;
+        }
+        Ok(values)
+    }
+
+    /// Evaluates a byte string constant, e.g. `b"counter"`.
+    fn const_bytes(&self, expr: &Expr) -> Option<Vec<u8>> {
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::ByteStr(bytes) => Some(bytes.value()),
+                _ => None,
+            },
+            Expr::Reference(reference) => self.const_bytes(&reference.expr),
+            Expr::MethodCall(call) if call.method == "as_bytes" => match call.receiver.as_ref() {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(s) => Some(s.value().into_bytes()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            Expr::Path(path) => {
+                let ident = path.path.get_ident()?;
+                let item = self.consts.iter().find(|x| &x.ident == ident)?;
+                self.const_bytes(&item.expr)
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve_discriminator(&self, spec: &DiscriminatorSpec) -> anyhow::Result<Vec<u8>> {
+        match spec {
+            DiscriminatorSpec::Hash { preimage } => {
+                Ok(Sha256::digest(preimage.as_bytes())[..8].to_vec())
+            }
+            DiscriminatorSpec::Value { expr, width } => {
+                let value = self
+                    .eval_int(expr)
+                    .ok_or_else(|| anyhow!("unsupported discriminator {}", quote::quote!(#expr)))?;
+                let width = match width {
+                    Some(width) => *width,
+                    None => self.enum_width(expr),
+                };
+                Ok(value.to_le_bytes()[..width].to_vec())
+            }
+        }
+    }
+
+    /// Returns the width of the `repr` of the enum an expression refers to.
+    fn enum_width(&self, expr: &Expr) -> usize {
+        let Expr::Path(path) = expr else {
+            return 1;
+        };
+        let segments = path.path.segments.iter().collect::<Vec<_>>();
+        let [.., e, _] = segments.as_slice() else {
+            return 1;
+        };
+        self.find_enum(&e.ident.to_string())
+            .and_then(|item| repr_width(&item.attrs))
+            .unwrap_or(1)
+    }
+
+    /// Collects the accounts of each instruction from the sdk functions, keyed by the name of
+    /// the instruction data struct.
+    fn sdk_accounts(&self) -> anyhow::Result<HashMap<String, Vec<IdlInstructionAccount>>> {
+        let mut instructions = HashMap::new();
+        for item in &self.fns {
+            let ReturnType::Type(_, ty) = &item.sig.output else {
+                continue;
+            };
+            if !matches!(ty.as_ref(), Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "Instruction"))
+            {
+                continue;
+            }
+
+            // Collect local bindings and the returned instruction.
+            let mut locals = HashMap::new();
+            let mut instruction = None;
+            for stmt in &item.block.stmts {
+                match stmt {
+                    Stmt::Local(local) => {
+                        if let (Pat::Ident(pat), Some(init)) = (&local.pat, &local.init) {
+                            locals.insert(pat.ident.to_string(), init.expr.as_ref());
+                        }
+                    }
+                    Stmt::Expr(Expr::Struct(expr), None) => instruction = Some(expr),
+                    _ => {}
+                }
+            }
+            let Some(instruction) = instruction else {
+                continue;
+            };
+
+            let mut data = None;
+            let mut accounts = vec![];
+            for field in &instruction.fields {
+                let Member::Named(member) = &field.member else {
+                    continue;
+                };
+                if member == "data" {
+                    data = data_struct(&field.expr, &locals);
+                } else if member == "accounts" {
+                    let Expr::Macro(mac) = &field.expr else {
+                        continue;
+                    };
+                    let metas = mac
+                        .mac
+                        .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+                    for (i, meta) in metas.iter().enumerate() {
+                        if let Some(account) = self.account_meta(meta, i, &locals) {
+                            accounts.push(account);
+                        }
+                    }
+                }
+            }
+            if let Some(data) = data {
+                instructions.insert(data, accounts);
+            }
+        }
+        Ok(instructions)
+    }
+
+    /// Parses an `AccountMeta::new(key, is_signer)` or `AccountMeta::new_readonly(..)` expression.
+    fn account_meta(
+        &self,
+        expr: &Expr,
+        index: usize,
+        locals: &HashMap<String, &Expr>,
+    ) -> Option<IdlInstructionAccount> {
+        let Expr::Call(call) = expr else {
+            return None;
+        };
+        let Expr::Path(func) = call.func.as_ref() else {
+            return None;
+        };
+        let writable = match func.path.segments.last()?.ident.to_string().as_str() {
+            "new" => true,
+            "new_readonly" => false,
+            _ => return None,
+        };
+        let key = call.args.first()?;
+        let signer = matches!(
+            call.args.iter().nth(1),
+            Some(Expr::Lit(syn::ExprLit { lit: Lit::Bool(b), .. })) if b.value
+        );
+
+        // Resolve local bindings, keeping the binding name.
+        let mut name = None;
+        let mut key = key;
+        if let Expr::Path(path) = key {
+            if let Some(ident) = path.path.get_ident() {
+                name = Some(ident.to_string());
+                if let Some(init) = locals.get(&ident.to_string()) {
+                    key = init;
+                }
+            }
+        }
+
+        let mut account = IdlInstructionAccount {
+            writable,
+            signer,
+            ..Default::default()
+        };
+        let (derived_name, address, pda) = self.analyze_key(key);
+        account.name = name
+            .or(derived_name)
+            .unwrap_or_else(|| format!("account_{}", index));
+        account.address = address;
+        account.pda = pda;
+        Some(account)
+    }
+
+    /// Derives a name, address and PDA from an account key expression.
+    fn analyze_key(&self, expr: &Expr) -> (Option<String>, Option<String>, Option<IdlPda>) {
+        match expr {
+            Expr::Reference(reference) => self.analyze_key(&reference.expr),
+            Expr::Paren(paren) => self.analyze_key(&paren.expr),
+            Expr::MethodCall(call) if call.method == "address" => self.analyze_pda(&call.receiver),
+            Expr::Field(field) => {
+                // e.g. `CounterPda { authority }.find().0`
+                if let Expr::MethodCall(call) = field.base.as_ref() {
+                    if call.method == "find" {
+                        return self.analyze_pda(&call.receiver);
+                    }
+                }
+
+                // e.g. `counter_pda().0`
+                let Expr::Call(call) = field.base.as_ref() else {
+                    return (None, None, None);
+                };
+                let Expr::Path(func) = call.func.as_ref() else {
+                    return (None, None, None);
+                };
+                let Some(func) = func.path.segments.last().map(|s| s.ident.to_string()) else {
+                    return (None, None, None);
+                };
+                let name = func.strip_suffix("_pda").unwrap_or(&func).to_string();
+                let args = call.args.iter().cloned().collect::<Vec<_>>();
+                (Some(name), None, self.resolve_pda(&func, &args))
+            }
+            Expr::Call(call) => match call.func.as_ref() {
+                Expr::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "id") => {
+                    self.analyze_id_path(&path.path)
+                }
+                _ => (None, None, None),
+            },
+            Expr::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "ID") => {
+                self.analyze_id_path(&path.path)
+            }
+            Expr::Path(path) => (path.path.get_ident().map(|i| i.to_string()), None, None),
+            _ => (None, None, None),
+        }
+    }
+
+    /// Resolves paths such as `system_program::ID` or `crate::ID` to a name and address.
+    fn analyze_id_path(
+        &self,
+        path: &syn::Path,
+    ) -> (Option<String>, Option<String>, Option<IdlPda>) {
+        let segments = path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>();
+        let module = match segments.as_slice() {
+            [.., module, _] => module.as_str(),
+            _ => "crate",
+        };
+        if module == "crate" {
+            return (Some("program".to_string()), self.address.clone(), None);
+        }
+        match KNOWN_ADDRESSES.iter().find(|(m, _, _)| *m == module) {
+            Some((_, name, address)) => (Some(name.to_string()), Some(address.to_string()), None),
+            None => (Some(module.to_string()), None, None),
+        }
+    }
+
+    /// Resolves the seeds of a `Pda` struct expression such as `CounterPda { authority, id }`.
+    ///
+    /// Seeds read from fields are emitted as `arg` seeds named after the field value, like the
+    /// arguments of `*_pda` functions.
+    fn analyze_pda(&self, expr: &Expr) -> (Option<String>, Option<String>, Option<IdlPda>) {
+        let (path, values) = match expr {
+            Expr::Paren(paren) => return self.analyze_pda(&paren.expr),
+            Expr::Struct(expr) => (&expr.path, expr.fields.iter().collect::<Vec<_>>()),
+            Expr::Path(path) => (&path.path, vec![]),
+            _ => return (None, None, None),
+        };
+        let Some(name) = path.segments.last().map(|s| s.ident.to_string()) else {
+            return (None, None, None);
+        };
+        let Some((_, seeds)) = self.pdas.iter().find(|(n, _)| *n == name) else {
+            return (None, None, None);
+        };
+        let fields = match self.find_struct(&name).map(|item| &item.fields) {
+            Some(Fields::Named(fields)) => fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref().map(|i| i.to_string()))
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        let mut idl_seeds = vec![];
+        for seed in seeds {
+            let field = match seed {
+                Expr::Path(path) => path
+                    .path
+                    .get_ident()
+                    .map(|i| i.to_string())
+                    .filter(|i| fields.contains(i)),
+                _ => None,
+            };
+            if let Some(field) = field {
+                let value = values.iter().find(|v| match &v.member {
+                    Member::Named(member) => *member == field,
+                    _ => false,
+                });
+                let path = value.and_then(|v| seed_ident(&v.expr)).unwrap_or(field);
+                idl_seeds.push(IdlSeed::Arg { path });
+            } else if let Some(value) = self.const_bytes(seed) {
+                idl_seeds.push(IdlSeed::Const { value });
+            } else {
+                return (None, None, None);
+            }
+        }
+        let name = to_snake_case(name.strip_suffix("Pda").unwrap_or(&name));
+        (Some(name), None, Some(IdlPda { seeds: idl_seeds }))
+    }
+
+    /// Resolves the seeds of a `*_pda` function called with the given arguments.
+    ///
+    /// Seeds that depend on arguments are emitted as `arg` seeds, and later reclassified as
+    /// `account` seeds if the instruction has an account of the same name.
+    fn resolve_pda(&self, func: &str, call_args: &[Expr]) -> Option<IdlPda> {
+        let item = self.find_fn(func)?;
+        let params = item
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                FnArg::Typed(arg) => match arg.pat.as_ref() {
+                    Pat::Ident(pat) => Some(pat.ident.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Find the seeds passed to `find_program_address`.
+        let Some(Stmt::Expr(Expr::Call(call), None)) = item.block.stmts.last() else {
+            return None;
+        };
+        let Expr::Path(path) = call.func.as_ref() else {
+            return None;
+        };
+        if path.path.segments.last()?.ident != "find_program_address" {
+            return None;
+        }
+        let mut seeds_expr = call.args.first()?;
+        while let Expr::Reference(reference) = seeds_expr {
+            seeds_expr = &reference.expr;
+        }
+        let Expr::Array(array) = seeds_expr else {
+            return None;
+        };
+
+        let mut seeds = vec![];
+        for seed in &array.elems {
+            if let Some(value) = self.const_bytes(seed) {
+                seeds.push(IdlSeed::Const { value });
+                continue;
+            }
+            let param = seed_ident(seed)?;
+            let index = params.iter().position(|p| *p == param)?;
+            let path = call_args.get(index).and_then(seed_ident).unwrap_or(param);
+            seeds.push(IdlSeed::Arg { path });
+        }
+        Some(IdlPda { seeds })
+    }
+}
+
+/// Returns the names listed in `#[derive(...)]` attributes.
+fn derives(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("derive"))
+        .filter_map(|a| {
+            a.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flat_map(|paths| {
+            paths
+                .into_iter()
+                .filter_map(|p| p.segments.last().map(|s| s.ident.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Parses the discriminator arguments of a derive helper attribute.
+fn discriminator_spec(
+    attrs: &[Attribute],
+    attr_name: &str,
+    default_namespace: &str,
+    hash_name: &str,
+) -> Option<DiscriminatorSpec> {
+    let mut expr = None;
+    let mut width = None;
+    let mut hash = false;
+    let mut namespace = default_namespace.to_string();
+    for attr in attrs.iter().filter(|a| a.path().is_ident(attr_name)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("discriminator") {
+                expr = Some(meta.value()?.parse::<Expr>()?);
+            } else if meta.path.is_ident("width") {
+                width = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("hash") {
+                hash = true;
+            } else if meta.path.is_ident("namespace") {
+                namespace = meta.value()?.parse::<syn::LitStr>()?.value();
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        })
+        .ok()?;
+    }
+    if hash {
+        return Some(DiscriminatorSpec::Hash {
+            preimage: format!("{}:{}", namespace, hash_name),
+        });
+    }
+    Some(DiscriminatorSpec::Value {
+        expr: expr?,
+        width: Some(width.unwrap_or(1)),
+    })
+}
+
+/// Returns the seeds listed in `#[pda(...)]`, excluding the program.
+fn pda_seeds(attrs: &[Attribute]) -> Option<Vec<Expr>> {
+    let attr = attrs.iter().find(|a| a.path().is_ident("pda"))?;
+    let exprs = attr
+        .parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        .ok()?;
+    Some(
+        exprs
+            .into_iter()
+            .filter(|e| !matches!(e, Expr::Assign(_)))
+            .collect(),
+    )
+}
+
+/// Returns the version declared by `#[account(version = N)]`, or 0.
+fn account_version(attrs: &[Attribute]) -> u8 {
+    let mut version = 0;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("account")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("version") {
+                version = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        });
+    }
+    version
+}
+
+/// Returns the width in bytes of an integer `#[repr(..)]`.
+fn repr_width(attrs: &[Attribute]) -> Option<usize> {
+    let attr = attrs.iter().find(|a| a.path().is_ident("repr"))?;
+    let ident: Ident = attr.parse_args().ok()?;
+    match ident.to_string().as_str() {
+        "u8" => Some(1),
+        "u16" => Some(2),
+        "u32" => Some(4),
+        "u64" => Some(8),
+        _ => None,
+    }
+}
+
+/// Returns the message of a `#[error("...")]` attribute.
+fn error_message(attrs: &[Attribute]) -> Option<String> {
+    let attr = attrs.iter().find(|a| a.path().is_ident("error"))?;
+    attr.parse_args::<syn::LitStr>().ok().map(|s| s.value())
+}
+
+/// Returns the name of the instruction data struct in `Add { .. }.to_bytes()`.
+fn data_struct(expr: &Expr, locals: &HashMap<String, &Expr>) -> Option<String> {
+    match expr {
+        Expr::MethodCall(call) if call.method == "to_bytes" => data_struct(&call.receiver, locals),
+        Expr::Struct(s) => s.path.segments.last().map(|s| s.ident.to_string()),
+        Expr::Paren(paren) => data_struct(&paren.expr, locals),
+        Expr::Path(path) => {
+            let ident = path.path.get_ident()?.to_string();
+            data_struct(locals.get(&ident)?, locals)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the identifier a seed expression such as `authority.as_ref()` is derived from.
+fn seed_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(|i| i.to_string()),
+        Expr::Reference(reference) => seed_ident(&reference.expr),
+        Expr::Paren(paren) => seed_ident(&paren.expr),
+        Expr::MethodCall(call) => seed_ident(&call.receiver),
+        Expr::Field(field) => seed_ident(&field.base),
+        _ => None,
+    }
+}
+
+/// Reclassifies `arg` seeds that refer to accounts of the instruction.
+fn classify_seeds(accounts: &mut [IdlInstructionAccount], args: &[IdlField]) {
+    let names = accounts.iter().map(|a| a.name.clone()).collect::<Vec<_>>();
+    for account in accounts.iter_mut() {
+        let Some(pda) = account.pda.as_mut() else {
+            continue;
+        };
+        for seed in pda.seeds.iter_mut() {
+            if let IdlSeed::Arg { path } = seed {
+                if names.contains(path) && !args.iter().any(|a| &a.name == path) {
+                    *seed = IdlSeed::Account { path: path.clone() };
+                }
+            }
+        }
+    }
+}
+
+/// Collects the names of all defined types referenced by the given fields.
+/// Returns the byte size of a `fixed` type name such as `U32F32`.
+fn fixed_point_size(name: &str) -> Option<usize> {
+    let (int_bits, frac_bits) = name.strip_prefix(['I', 'U'])?.split_once('F')?;
+    let bits = int_bits.parse::<usize>().ok()? + frac_bits.parse::<usize>().ok()?;
+    matches!(bits, 8 | 16 | 32 | 64 | 128).then_some(bits / 8)
+}
+
+/// Returns the name of the type argument of a generic type, e.g. `U32F32` in `PodFixed<U32F32>`.
+fn fixed_generic(segment: &syn::PathSegment) -> Option<String> {
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(Type::Path(path)) => {
+            Some(path.path.segments.last()?.ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn bytes_type(len: usize) -> IdlType {
+    IdlType::Array {
+        array: (Box::new(IdlType::Primitive("u8".to_string())), len),
+    }
+}
+
+fn collect_defined(fields: &[IdlField], defined: &mut BTreeSet<String>) {
+    fn visit(ty: &IdlType, defined: &mut BTreeSet<String>) {
+        match ty {
+            IdlType::Primitive(_) => {}
+            IdlType::Array { array } => visit(&array.0, defined),
+            IdlType::Defined { defined: d } => {
+                defined.insert(d.name.clone());
+            }
+        }
+    }
+    for field in fields {
+        visit(&field.ty, defined);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template_sources() -> Vec<(PathBuf, String)> {
+        let templates = [
+            ("lib.rs", include_str!("../template/api_src_lib_rs")),
+            ("consts.rs", include_str!("../template/api_src_consts_rs")),
+            ("error.rs", include_str!("../template/api_src_error_rs")),
+            (
+                "instruction.rs",
+                include_str!("../template/api_src_instruction_rs"),
+            ),
+            ("sdk.rs", include_str!("../template/api_src_sdk_rs")),
+            (
+                "state/mod.rs",
+                include_str!("../template/api_src_state_mod_rs"),
+            ),
+            (
+                "state/counter.rs",
+                include_str!("../template/api_src_state_counter_rs"),
+            ),
+        ];
+        templates
+            .iter()
+            .map(|(path, source)| {
+                (
+                    PathBuf::from(path),
+                    source.replace("{name_typecase}", "Foo"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_template() {
+        let idl = parse_sources(&template_sources()).unwrap();
+        assert_eq!(idl.address, "z7msBPQHDJjTvdQRoEcKyENgXDhSRYeHieN1ZMTqo35");
+
+        // Accounts
+        assert_eq!(idl.accounts.len(), 1);
+        assert_eq!(idl.accounts[0].name, "Counter");
+        assert_eq!(idl.accounts[0].discriminator, vec![0; 5]);
+
+        // Instructions
+        assert_eq!(idl.instructions.len(), 2);
+        let add = idl.instructions.iter().find(|i| i.name == "add").unwrap();
+        assert_eq!(add.discriminator, vec![1]);
+        assert_eq!(add.args[0].ty, IdlType::Primitive("u64".to_string()));
+        let initialize = idl
+            .instructions
+            .iter()
+            .find(|i| i.name == "initialize")
+            .unwrap();
+        let names = initialize
+            .accounts
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["signer", "counter", "system_program"]);
+        assert!(initialize.accounts[0].signer);
+        assert_eq!(
+            initialize.accounts[1].pda.as_ref().unwrap().seeds,
+            vec![IdlSeed::Const {
+                value: b"counter".to_vec()
+            }]
+        );
+        assert_eq!(
+            initialize.accounts[2].address.as_deref(),
+            Some("11111111111111111111111111111111")
+        );
+
+        // Errors
+        assert_eq!(idl.errors.len(), 1);
+        assert_eq!(idl.errors[0].msg.as_deref(), Some("This is a dummy error"));
+
+        // Types
+        assert_eq!(idl.types.len(), 1);
+        assert_eq!(idl.types[0].name, "Counter");
+    }
+
+    #[test]
+    fn parse_derives() {
+        let source = r#"
+            #[repr(C)]
+            #[derive(Clone, Copy, Pod, Zeroable, Account)]
+            #[account(discriminator = 300, width = 2)]
+            pub struct Profile {
+                pub authority: Pubkey,
+                pub balance: Numeric,
+                pub name: PodString<NAME_LEN>,
+                pub active: PodBool,
+                pub rate: PodU32F32,
+                pub supply: PodFixed<U80F48>,
+            }
+
+            pub const NAME_LEN: usize = 32;
+
+            #[repr(C)]
+            #[derive(Clone, Copy, Pod, Zeroable, Instruction)]
+            #[instruction(hash)]
+            pub struct InitializeProfile {
+                pub id: u64,
+            }
+
+            #[repr(C)]
+            #[derive(Clone, Copy, Pod, Zeroable, Event)]
+            pub struct ProfileEvent {
+                pub id: u64,
+            }
+
+            pub fn profile_pda(authority: Pubkey, id: u64) -> (Pubkey, u8) {
+                Pubkey::find_program_address(&[b"profile", authority.as_ref(), &id.to_le_bytes()], &crate::ID)
+            }
+
+            pub fn initialize_profile(authority: Pubkey, id: u64) -> Instruction {
+                let profile = profile_pda(authority, id).0;
+                Instruction {
+                    program_id: crate::ID,
+                    accounts: vec![
+                        AccountMeta::new(authority, true),
+                        AccountMeta::new(profile, false),
+                    ],
+                    data: InitializeProfile { id }.to_bytes(),
+                }
+            }
+        "#;
+        let idl = parse_sources(&[(PathBuf::from("lib.rs"), source.to_string())]).unwrap();
+        assert_eq!(idl.accounts[0].discriminator, vec![44, 1, 0, 0, 0]);
+        assert_eq!(
+            idl.instructions[0].discriminator,
+            Sha256::digest(b"global:initialize_profile")[..8].to_vec()
+        );
+        assert_eq!(
+            idl.instructions[0].accounts[1].pda.as_ref().unwrap().seeds,
+            vec![
+                IdlSeed::Const {
+                    value: b"profile".to_vec()
+                },
+                IdlSeed::Account {
+                    path: "authority".to_string()
+                },
+                IdlSeed::Arg {
+                    path: "id".to_string()
+                },
+            ]
+        );
+        let profile = idl.types.iter().find(|t| t.name == "Profile").unwrap();
+        assert_eq!(
+            profile.ty.fields[2].ty,
+            IdlType::Array {
+                array: (Box::new(IdlType::Primitive("u8".to_string())), 32)
+            }
+        );
+        assert_eq!(
+            profile.ty.fields[3].ty,
+            IdlType::Primitive("bool".to_string())
+        );
+        assert_eq!(profile.ty.fields[4].ty, bytes_type(8));
+        assert_eq!(profile.ty.fields[5].ty, bytes_type(16));
+        assert_eq!(idl.events[0].name, "ProfileEvent");
+        let types = idl
+            .types
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec!["Numeric", "Profile", "ProfileEvent"]);
+    }
+    #[test]
+    fn parse_account_version() {
+        let source = r#"
+            #[repr(C)]
+            #[derive(Clone, Copy, Pod, Zeroable, Account)]
+            #[account(discriminator = 0)]
+            pub struct CounterV0 {
+                pub value: u64,
+            }
+
+            #[repr(C)]
+            #[derive(Clone, Copy, Pod, Zeroable, Account)]
+            #[account(discriminator = 0, version = 2, migrate_from(CounterV0))]
+            pub struct Counter {
+                pub value: u64,
+                pub limit: u64,
+            }
+        "#;
+        let idl = parse_sources(&[(PathBuf::from("lib.rs"), source.to_string())]).unwrap();
+        assert_eq!(idl.accounts[0].discriminator, vec![0; 5]);
+        assert_eq!(idl.accounts[1].name, "Counter");
+        assert_eq!(idl.accounts[1].discriminator, vec![0, 0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn parse_pda_derive() {
+        let source = r#"
+            pub const COUNTER: &[u8] = b"counter";
+
+            #[derive(Pda)]
+            #[pda(COUNTER, authority, id)]
+            pub struct CounterPda {
+                pub authority: Pubkey,
+                pub id: u64,
+            }
+
+            #[derive(Pda)]
+            #[pda(b"config", program = crate::ID)]
+            pub struct ConfigPda;
+
+            #[repr(C)]
+            #[derive(Clone, Copy, Pod, Zeroable, Instruction)]
+            #[instruction(discriminator = 0)]
+            pub struct Initialize {
+                pub id: u64,
+            }
+
+            pub fn initialize(authority: Pubkey, id: u64) -> Instruction {
+                Instruction {
+                    program_id: crate::ID,
+                    accounts: vec![
+                        AccountMeta::new(authority, true),
+                        AccountMeta::new(CounterPda { authority, id }.address(), false),
+                        AccountMeta::new_readonly(ConfigPda.find().0, false),
+                    ],
+                    data: Initialize { id }.to_bytes(),
+                }
+            }
+        "#;
+        let idl = parse_sources(&[(PathBuf::from("lib.rs"), source.to_string())]).unwrap();
+        let accounts = &idl.instructions[0].accounts;
+        assert_eq!(accounts[1].name, "counter");
+        assert_eq!(
+            accounts[1].pda.as_ref().unwrap().seeds,
+            vec![
+                IdlSeed::Const {
+                    value: b"counter".to_vec()
+                },
+                IdlSeed::Account {
+                    path: "authority".to_string()
+                },
+                IdlSeed::Arg {
+                    path: "id".to_string()
+                },
+            ]
+        );
+        assert_eq!(accounts[2].name, "config");
+        assert_eq!(
+            accounts[2].pda.as_ref().unwrap().seeds,
+            vec![IdlSeed::Const {
+                value: b"config".to_vec()
+            }]
+        );
+    }
+}
@@ -1,7 +1,9 @@
 mod args;
 mod build_project;
 mod clean_project;
+mod client;
 mod config;
+mod idl;
 mod new_project;
 mod program_keys;
 mod test_project;
@@ -11,7 +13,9 @@ use args::*;
 use build_project::*;
 use clap::{command, Parser, Subcommand};
 use clean_project::*;
+use client::*;
 use config::{load_client_and_signer, CommitmentLevel};
+use idl::*;
 use new_project::*;
 use program_keys::*;
 use test_project::*;
@@ -30,6 +34,12 @@ enum Command {
     #[command(about = "Remove artifacts cargo has generated in the past")]
     Clean(CleanArgs),
 
+    #[command(about = "Generate an IDL for the program")]
+    Idl(IdlArgs),
+
+    #[command(about = "Generate a typed client for the program")]
+    Client(ClientArgs),
+
     #[command(about = "Program Keypair commands.")]
     Keys(ProgramKeysArgs),
 }
@@ -69,6 +79,8 @@ fn main() -> anyhow::Result<()> {
         Command::Clean(args) => clean_project(args),
         Command::New(args) => new_project(args),
         Command::Test(args) => test_project(args),
+        Command::Idl(args) => idl_project(args),
+        Command::Client(args) => client_project(args),
         Command::Keys(args) => match args.command {
             KeysSubcommand::List => list_keypair(args),
             KeysSubcommand::New => new_keypair(args),
@@ -47,6 +47,21 @@ pub fn to_lib_case(input: &str) -> String {
     input.to_ascii_lowercase().replace("-", "_")
 }
 
+pub fn to_snake_case(input: &str) -> String {
+    let mut snake_case_string = String::new();
+    for (i, c) in input.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake_case_string.push('_');
+            }
+            snake_case_string.extend(c.to_lowercase());
+        } else {
+            snake_case_string.push(c);
+        }
+    }
+    snake_case_string
+}
+
 pub fn capitalize_first(input: &str) -> String {
     let mut c = input.chars();
     match c.next() {
@@ -0,0 +1,137 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitInt, Path};
+
+use crate::utils::{assert_pod, parse_discriminator, validate_struct, ACCOUNT_NAMESPACE};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    validate_struct(&input, "Account")?;
+    let mut version = None;
+    let mut migrate_from = Vec::<Path>::new();
+    let (discriminator, discriminator_bytes, width) = parse_discriminator(
+        &input.attrs,
+        "account",
+        input.ident.span(),
+        ACCOUNT_NAMESPACE,
+        &input.ident.to_string(),
+        |meta| {
+            if meta.path.is_ident("version") {
+                let lit = meta.value()?.parse::<LitInt>()?;
+                version = Some((lit.base10_parse::<u8>()?, lit.span()));
+            } else if meta.path.is_ident("migrate_from") {
+                meta.parse_nested_meta(|old| {
+                    migrate_from.push(old.path);
+                    Ok(())
+                })?;
+            } else {
+                return Ok(false);
+            }
+            Ok(true)
+        },
+    )?;
+    if let Some((_, span)) = version.filter(|_| width > 4) {
+        return Err(syn::Error::new(
+            span,
+            "versioned accounts need a discriminator of at most 4 bytes",
+        ));
+    }
+    if version.is_none() && !migrate_from.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "migrate_from requires an account version",
+        ));
+    }
+    if !migrate_from.is_empty() && !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "migrate_from cannot be used on generic accounts",
+        ));
+    }
+    let version_value = version.map(|(v, _)| v).unwrap_or(0);
+    let version = version.map(|(v, _)| {
+        quote! {
+            fn version() -> u8 {
+                #v
+            }
+        }
+    });
+    let pod_assertion = assert_pod(&input);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let migrate = (!migrate_from.is_empty()).then(|| {
+        quote! {
+            // Old layouts must share the discriminator and have a lower version.
+            #(
+                const _: () = {
+                    let old = <#migrate_from>::DISCRIMINATOR;
+                    let new = <#name>::DISCRIMINATOR;
+                    assert!(
+                        old.len() == new.len(),
+                        "migrate_from types must have the same discriminator"
+                    );
+                    let mut i = 0;
+                    while i < old.len() {
+                        assert!(
+                            old[i] == new[i],
+                            "migrate_from types must have the same discriminator"
+                        );
+                        i += 1;
+                    }
+                    assert!(
+                        <#migrate_from>::VERSION < <#name>::VERSION,
+                        "migrate_from types must have a lower version"
+                    );
+                };
+            )*
+
+            impl #impl_generics ::steel::AccountMigrate for #name #ty_generics #where_clause {
+                #[track_caller]
+                fn migrate(version: u8, data: &[u8]) -> Result<Self, ::steel::ProgramError> {
+                    #(
+                        if version == <#migrate_from as ::steel::Discriminator>::version() {
+                            let old =
+                                <#migrate_from as ::steel::AccountDeserialize>::try_from_bytes(data)?;
+                            return Ok(<Self as ::core::convert::From<#migrate_from>>::from(*old));
+                        }
+                    )*
+                    Err(::steel::trace(
+                        format!("No migration from account version {}", version).as_str(),
+                        ::steel::ProgramError::InvalidAccountData,
+                    ))
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #pod_assertion
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub const SIZE: usize = 8 + ::core::mem::size_of::<Self>();
+
+            /// The discriminator written at the start of the account header.
+            pub const DISCRIMINATOR: &'static [u8] = {
+                const DISCRIMINATOR: #discriminator_bytes;
+                &DISCRIMINATOR
+            };
+
+            /// The version of the account layout.
+            pub const VERSION: u8 = #version_value;
+
+            pub fn to_bytes(&self) -> &[u8] {
+                ::steel::bytemuck::bytes_of(self)
+            }
+        }
+
+        impl #impl_generics ::steel::Discriminator for #name #ty_generics #where_clause {
+            #discriminator
+            #version
+        }
+
+        #migrate
+
+        impl #impl_generics ::steel::AccountValidation for #name #ty_generics #where_clause {}
+    })
+}
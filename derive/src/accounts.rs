@@ -0,0 +1,205 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::ParseStream, spanned::Spanned, Data, DeriveInput, Expr, Fields, GenericArgument, Ident,
+    Lifetime, PathArguments, Token, Type,
+};
+
+use crate::utils::parse_seeds;
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "#[derive(Accounts)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "#[derive(Accounts)] can only be used on structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(syn::Error::new(
+            name.span(),
+            "#[derive(Accounts)] requires at least one account",
+        ));
+    }
+
+    // Resolve the slice and account info lifetimes from the first field, checking every field has
+    // the type of an account or the remaining accounts.
+    let mut lifetimes = None;
+    let mut idents = Vec::new();
+    let mut checks = Vec::new();
+    let mut remaining = None;
+    for (i, field) in fields.iter().enumerate() {
+        let ident = field.ident.as_ref().unwrap();
+        if field.attrs.iter().any(|a| a.path().is_ident("remaining")) {
+            if i + 1 != fields.len() {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "#[remaining] must be the last field",
+                ));
+            }
+            let field_lifetimes = account_info_lifetimes(&field.ty, true)?;
+            lifetimes.get_or_insert(field_lifetimes);
+            remaining = Some(ident);
+            continue;
+        }
+        let field_lifetimes = account_info_lifetimes(&field.ty, false)?;
+        lifetimes.get_or_insert(field_lifetimes);
+        idents.push(ident);
+
+        let mut validations = Vec::new();
+        for attr in &field.attrs {
+            let path = attr.path();
+            let validation = if path.is_ident("signer") {
+                attr.meta.require_path_only()?;
+                quote! { #ident.is_signer()?; }
+            } else if path.is_ident("writable") {
+                attr.meta.require_path_only()?;
+                quote! { #ident.is_writable()?; }
+            } else if path.is_ident("executable") {
+                attr.meta.require_path_only()?;
+                quote! { #ident.is_executable()?; }
+            } else if path.is_ident("empty") {
+                attr.meta.require_path_only()?;
+                quote! { #ident.is_empty()?; }
+            } else if path.is_ident("address") {
+                let address: Expr = attr.parse_args()?;
+                quote! { #ident.has_address(&#address)?; }
+            } else if path.is_ident("owner") {
+                let owner: Expr = attr.parse_args()?;
+                quote! { #ident.has_owner(&#owner)?; }
+            } else if path.is_ident("program") {
+                let program: Expr = attr.parse_args()?;
+                quote! { #ident.is_program(&#program)?; }
+            } else if path.is_ident("sysvar") {
+                let sysvar: Expr = attr.parse_args()?;
+                quote! { #ident.is_sysvar(&#sysvar)?; }
+            } else if path.is_ident("seeds") {
+                let (seeds, program) = attr.parse_args_with(parse_seeds)?;
+                quote! { #ident.has_seeds(&[#(#seeds),*], &#program)?; }
+            } else if path.is_ident("account") {
+                let (ty, owner) = attr.parse_args_with(parse_account)?;
+                quote! { #ident.as_account::<#ty>(&#owner)?; }
+            } else {
+                continue;
+            };
+            validations.push(validation);
+        }
+
+        if !validations.is_empty() {
+            let label = ident.to_string();
+            checks.push(quote_spanned! {ident.span()=>
+                let result: Result<(), ::steel::ProgramError> = (|| {
+                    #(#validations)*
+                    Ok(())
+                })();
+                if let Err(err) = result {
+                    return Err(::steel::trace(
+                        format!("Account {} ({}) is invalid", #i, #label).as_str(),
+                        err,
+                    ));
+                }
+            });
+        }
+    }
+
+    let (a, info) = lifetimes.expect("struct has at least one field");
+    let pattern = match remaining {
+        Some(remaining) => quote! { [#(#idents,)* #remaining @ ..] },
+        None => quote! { [#(#idents),*] },
+    };
+    let remaining = remaining.map(|r| quote! { #r, });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<&#a [::steel::AccountInfo<#info>]> for #name #ty_generics #where_clause {
+            type Error = ::steel::ProgramError;
+
+            fn try_from(accounts: &#a [::steel::AccountInfo<#info>]) -> Result<Self, Self::Error> {
+                #[allow(unused_imports)]
+                use ::steel::{AccountInfoValidation, AsAccount};
+
+                let #pattern = accounts else {
+                    return Err(::steel::trace(
+                        "Not enough account keys",
+                        ::steel::ProgramError::NotEnoughAccountKeys,
+                    ));
+                };
+
+                #(#checks)*
+
+                Ok(Self {
+                    #(#idents,)*
+                    #remaining
+                })
+            }
+        }
+    })
+}
+
+/// Extracts `'a` and `'info` from a field of type `&'a AccountInfo<'info>`, or
+/// `&'a [AccountInfo<'info>]` for the remaining accounts.
+fn account_info_lifetimes(ty: &Type, remaining: bool) -> syn::Result<(Lifetime, Lifetime)> {
+    let err = || {
+        let msg = if remaining {
+            "#[remaining] fields must be of type &'a [AccountInfo<'info>]"
+        } else {
+            "#[derive(Accounts)] fields must be of type &'a AccountInfo<'info>"
+        };
+        syn::Error::new(ty.span(), msg)
+    };
+    let Type::Reference(reference) = ty else {
+        return Err(err());
+    };
+    let a = reference.lifetime.clone().ok_or_else(err)?;
+    let elem = match (reference.elem.as_ref(), remaining) {
+        (Type::Slice(slice), true) => slice.elem.as_ref(),
+        (elem, false) => elem,
+        _ => return Err(err()),
+    };
+    let Type::Path(path) = elem else {
+        return Err(err());
+    };
+    let segment = path.path.segments.last().ok_or_else(err)?;
+    if segment.ident != "AccountInfo" {
+        return Err(err());
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(err());
+    };
+    match args.args.first() {
+        Some(GenericArgument::Lifetime(info)) => Ok((a, info.clone())),
+        _ => Err(err()),
+    }
+}
+
+/// Parses `#[account(Counter, owner = ID)]`. The owner defaults to `crate::ID`.
+fn parse_account(input: ParseStream) -> syn::Result<(Type, Expr)> {
+    let ty: Type = input.parse()?;
+    let mut owner = None;
+    while !input.is_empty() {
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            break;
+        }
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if key != "owner" {
+            return Err(syn::Error::new(key.span(), "unknown account attribute"));
+        }
+        owner = Some(input.parse::<Expr>()?);
+    }
+    Ok((ty, owner.unwrap_or_else(|| syn::parse_quote!(crate::ID))))
+}
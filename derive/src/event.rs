@@ -0,0 +1,37 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::utils::{assert_pod, validate_struct};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    validate_struct(&input, "Event")?;
+    let pod_assertion = assert_pod(&input);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #pod_assertion
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn to_bytes(&self) -> &[u8] {
+                ::steel::bytemuck::bytes_of(self)
+            }
+
+            pub fn from_bytes(data: &[u8]) -> &Self {
+                ::steel::bytemuck::from_bytes::<Self>(data)
+            }
+        }
+
+        impl #impl_generics ::steel::Loggable for #name #ty_generics #where_clause {
+            fn log(&self) {
+                ::steel::solana_program::log::sol_log_data(&[self.to_bytes()]);
+            }
+
+            fn log_return(&self) {
+                ::steel::solana_program::program::set_return_data(self.to_bytes());
+            }
+        }
+    })
+}
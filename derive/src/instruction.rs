@@ -0,0 +1,46 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::utils::{
+    assert_pod, parse_discriminator, to_snake_case, validate_struct, INSTRUCTION_NAMESPACE,
+};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    validate_struct(&input, "Instruction")?;
+    let (discriminator, _, _) = parse_discriminator(
+        &input.attrs,
+        "instruction",
+        input.ident.span(),
+        INSTRUCTION_NAMESPACE,
+        &to_snake_case(&input.ident.to_string()),
+        |_| Ok(false),
+    )?;
+    let pod_assertion = assert_pod(&input);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #pod_assertion
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn try_from_bytes(data: &[u8]) -> Result<&Self, ::steel::ProgramError> {
+                ::steel::bytemuck::try_from_bytes::<Self>(data)
+                    .or(Err(::steel::ProgramError::InvalidInstructionData))
+            }
+
+            pub fn to_bytes(&self) -> Vec<u8> {
+                [
+                    <Self as ::steel::Discriminator>::discriminator_bytes().to_vec(),
+                    ::steel::bytemuck::bytes_of(self).to_vec(),
+                ]
+                .concat()
+            }
+        }
+
+        impl #impl_generics ::steel::Discriminator for #name #ty_generics #where_clause {
+            #discriminator
+        }
+    })
+}
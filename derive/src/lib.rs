@@ -0,0 +1,147 @@
+mod account;
+mod accounts;
+mod event;
+mod instruction;
+mod pda;
+mod utils;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Links an account struct with a discriminator and implements basic serialization logic.
+///
+/// Generates the same items as the `account!` macro: `Discriminator`, `AccountValidation`,
+/// `to_bytes` and `SIZE`, as well as the `DISCRIMINATOR` and `VERSION` constants.
+///
+/// The discriminator is 1 byte wide by default. Use `width = 2`, `4` or `8` for wider
+/// discriminators, or `hash` to derive an Anchor-compatible 8 byte discriminator from
+/// `sha256("account:<Name>")`. The hash namespace can be changed with `namespace = "..."`.
+///
+/// Accounts with a discriminator of at most 4 bytes can set `version = N`, which is stored in the
+/// account header. `migrate_from(OldType, ..)` implements `AccountMigrate` from prior versions
+/// with the same discriminator, using their `From` conversions. The prior versions must also
+/// derive `Account`, and a different discriminator or a version that isn't lower fails to compile.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+/// #[account(discriminator = 0)]
+/// pub struct Counter {
+///     pub value: u64,
+/// }
+/// ```
+#[proc_macro_derive(Account, attributes(account))]
+pub fn derive_account(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    account::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Parses and validates the accounts of an instruction.
+///
+/// Implements `TryFrom<&[AccountInfo]>` for a struct of `&'a AccountInfo<'info>` fields. Accounts
+/// are destructured in field order, and the checks declared on each field are run in the order
+/// they are written. If a check fails, the error is traced with the index and name of the account.
+///
+/// Supported field attributes:
+/// - `#[signer]`, `#[writable]`, `#[executable]`, `#[empty]`
+/// - `#[address(ID)]`, `#[owner(ID)]`, `#[program(ID)]`, `#[sysvar(ID)]`
+/// - `#[seeds(SEED, other.key.as_ref(), program = ID)]`, where `program` defaults to `crate::ID`
+/// - `#[account(Type, owner = ID)]`, where `owner` defaults to `crate::ID`
+/// - `#[remaining]` on a trailing `&'a [AccountInfo<'info>]` field to collect any extra accounts
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// pub struct AddAccounts<'a, 'info> {
+///     #[signer]
+///     pub signer: &'a AccountInfo<'info>,
+///     #[writable]
+///     #[seeds(COUNTER)]
+///     #[account(Counter)]
+///     pub counter: &'a AccountInfo<'info>,
+/// }
+///
+/// let accounts = AddAccounts::try_from(accounts)?;
+/// ```
+#[proc_macro_derive(
+    Accounts,
+    attributes(
+        signer, writable, executable, empty, address, owner, program, sysvar, seeds, account,
+        remaining
+    )
+)]
+pub fn derive_accounts(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    accounts::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Links instruction data with a discriminator and implements basic serialization logic.
+///
+/// Generates the same items as the `instruction!` macro: `Discriminator`, `to_bytes` and
+/// `try_from_bytes`.
+///
+/// Accepts the same discriminator arguments as `#[derive(Account)]`. Hashed instruction
+/// discriminators default to the Anchor `sha256("global:<snake_name>")` preimage.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, Pod, Zeroable, Instruction)]
+/// #[instruction(discriminator = 1)]
+/// pub struct Add {
+///     pub amount: [u8; 8],
+/// }
+/// ```
+#[proc_macro_derive(Instruction, attributes(instruction))]
+pub fn derive_instruction(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    instruction::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements serialization and logging logic for an event struct.
+///
+/// Generates the same items as the `event!` macro: `Loggable`, `to_bytes` and `from_bytes`.
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Event)]
+/// pub struct MyEvent {
+///     pub value: u64,
+/// }
+/// ```
+#[proc_macro_derive(Event)]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    event::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Declares a program derived address with typed seeds.
+///
+/// Implements `Pda`, which provides `find`, `address`, `validate`, `signer_seeds`,
+/// `create_account` and `invoke_signed`. Seeds are listed in `#[pda(...)]` in derivation order.
+/// Seeds naming a field of the struct are read from it via `PdaSeed`, and any other seed is a
+/// constant byte string. The program defaults to `crate::ID` and can be set with `program = ID`.
+///
+/// ```ignore
+/// #[derive(Pda)]
+/// #[pda(COUNTER, authority, id)]
+/// pub struct CounterPda {
+///     pub authority: Pubkey,
+///     pub id: u64,
+/// }
+///
+/// let (address, bump) = CounterPda { authority, id }.find();
+/// ```
+#[proc_macro_derive(Pda, attributes(pda))]
+pub fn derive_pda(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    pda::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,73 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, Fields};
+
+use crate::utils::parse_seeds;
+
+/// The maximum number of seeds of a PDA, excluding the bump.
+const MAX_SEEDS: usize = 16;
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unit => vec![],
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "#[derive(Pda)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "#[derive(Pda)] can only be used on structs",
+            ))
+        }
+    };
+
+    let attr = input
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("pda"))
+        .ok_or_else(|| syn::Error::new(name.span(), "missing #[pda(...)] attribute"))?;
+    let (seeds, program) = attr.parse_args_with(parse_seeds)?;
+    if seeds.is_empty() || seeds.len() > MAX_SEEDS {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("a PDA needs between 1 and {} seeds", MAX_SEEDS),
+        ));
+    }
+
+    // Seeds naming a field are read from the struct, anything else is a constant.
+    let seeds = seeds.iter().map(|seed| {
+        let field = match seed {
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| fields.iter().find(|f| f.ident.as_ref() == Some(ident))),
+            _ => None,
+        };
+        match field {
+            Some(field) => {
+                let ident = &field.ident;
+                quote! { ::steel::PdaSeed::as_seed(&self.#ident) }
+            }
+            None => quote! { ::core::convert::AsRef::<[u8]>::as_ref(&#seed) },
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::steel::Pda for #name #ty_generics #where_clause {
+            const PROGRAM_ID: ::steel::Pubkey = #program;
+
+            fn seeds(&self) -> ::steel::PdaSeeds<'_> {
+                ::steel::PdaSeeds::new()#(.with(#seeds))*
+            }
+        }
+    })
+}
@@ -0,0 +1,220 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use sha2::{Digest, Sha256};
+use syn::{
+    meta::ParseNestedMeta, parse::ParseStream, punctuated::Punctuated, Attribute, Data,
+    DeriveInput, Expr, ExprLit, Lit, LitInt, LitStr, Token,
+};
+
+/// Validates the input is a `#[repr(C)]` struct.
+///
+/// Pod types must have a well defined layout, so anything else is rejected with an error
+/// pointing at the offending item.
+pub fn validate_struct(input: &DeriveInput, derive_name: &str) -> syn::Result<()> {
+    if !matches!(input.data, Data::Struct(_)) {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            format!("#[derive({})] can only be used on structs", derive_name),
+        ));
+    }
+
+    let mut is_repr_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        let reprs = attr.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Meta, Token![,]>::parse_terminated,
+        )?;
+        is_repr_c |= reprs
+            .iter()
+            .any(|r| r.path().is_ident("C") || r.path().is_ident("transparent"));
+    }
+    if !is_repr_c {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            format!(
+                "#[derive({})] requires the struct to be #[repr(C)]",
+                derive_name
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Emits a compile time assertion that the type implements `Pod`.
+pub fn assert_pod(input: &DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote_spanned! {name.span()=>
+        #[allow(dead_code)]
+        const _: () = {
+            fn assert_pod<T: ::steel::Pod>() {}
+            fn assert_all #impl_generics () #where_clause {
+                assert_pod::<#name #ty_generics>();
+            }
+        };
+    }
+}
+
+/// The namespace used for hashed account discriminators, matching Anchor.
+pub const ACCOUNT_NAMESPACE: &str = "account";
+
+/// The namespace used for hashed instruction discriminators, matching Anchor.
+pub const INSTRUCTION_NAMESPACE: &str = "global";
+
+/// Parses the discriminator of a helper attribute such as `#[account(discriminator = 0)]` and
+/// returns the body of a `Discriminator` implementation.
+///
+/// Supported arguments:
+/// - `discriminator = <expr>` sets the discriminator value, stored little-endian.
+/// - `width = 1 | 2 | 4 | 8` sets the discriminator width in bytes. Defaults to 1.
+/// - `hash` derives an 8 byte discriminator from `sha256("<namespace>:<name>")`.
+/// - `namespace = "<namespace>"` overrides the default hash namespace.
+///
+/// Other arguments are passed to `extra`, which returns whether it handled them. Returns the
+/// implementation body, the discriminator as a `[u8; N] = <expr>` const item type and value, and
+/// the discriminator width in bytes.
+pub fn parse_discriminator(
+    attrs: &[Attribute],
+    attr_name: &str,
+    span: Span,
+    default_namespace: &str,
+    hash_name: &str,
+    mut extra: impl FnMut(&ParseNestedMeta) -> syn::Result<bool>,
+) -> syn::Result<(TokenStream, TokenStream, usize)> {
+    let mut value = None;
+    let mut width = None;
+    let mut hash = false;
+    let mut namespace = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident(attr_name)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("discriminator") {
+                value = Some(meta.value()?.parse::<Expr>()?);
+            } else if meta.path.is_ident("width") {
+                let lit = meta.value()?.parse::<LitInt>()?;
+                let w = lit.base10_parse::<usize>()?;
+                if ![1, 2, 4, 8].contains(&w) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "discriminator width must be 1, 2, 4 or 8 bytes",
+                    ));
+                }
+                width = Some((w, lit.span()));
+            } else if meta.path.is_ident("hash") {
+                hash = true;
+            } else if meta.path.is_ident("namespace") {
+                namespace = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if !extra(&meta)? {
+                return Err(meta.error(format!("unknown {} attribute", attr_name)));
+            }
+            Ok(())
+        })?;
+    }
+
+    let (bytes, width) = match (value, hash) {
+        (Some(value), false) => {
+            if namespace.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "namespace can only be used with hashed discriminators",
+                ));
+            }
+            let width = width.map(|(w, _)| w).unwrap_or(1);
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) = &value
+            {
+                let v = lit.base10_parse::<u128>()?;
+                if v >> (8 * width) != 0 {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("discriminator does not fit in {} byte(s)", width),
+                    ));
+                }
+            }
+            let ty = format_ident!("u{}", 8 * width);
+            (
+                quote! { [u8; #width] = ((#value) as #ty).to_le_bytes() },
+                width,
+            )
+        }
+        (None, true) => {
+            if let Some((w, span)) = width.filter(|(w, _)| *w != 8) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("hashed discriminators are 8 bytes, not {}", w),
+                ));
+            }
+            let namespace = namespace.as_deref().unwrap_or(default_namespace);
+            let preimage = format!("{}:{}", namespace, hash_name);
+            let digest = Sha256::digest(preimage.as_bytes());
+            let bytes = &digest[..8];
+            (quote! { [u8; 8] = [#(#bytes),*] }, 8)
+        }
+        (Some(_), true) => {
+            return Err(syn::Error::new(
+                span,
+                "discriminator and hash cannot be used together",
+            ))
+        }
+        (None, false) => {
+            return Err(syn::Error::new(
+                span,
+                format!("missing #[{}(discriminator = ...)] attribute", attr_name),
+            ))
+        }
+    };
+
+    Ok((
+        quote! {
+            fn discriminator() -> u8 {
+                <Self as ::steel::Discriminator>::discriminator_bytes()[0]
+            }
+
+            fn discriminator_bytes() -> &'static [u8] {
+                const DISCRIMINATOR: #bytes;
+                &DISCRIMINATOR
+            }
+        },
+        bytes,
+        width,
+    ))
+}
+
+/// Converts a type name to snake case, e.g. `InitializeMint` to `initialize_mint`.
+pub fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Parses seeds such as `#[seeds(SEED_A, seed_b, program = ID)]`. The program defaults to
+/// `crate::ID`.
+pub fn parse_seeds(input: ParseStream) -> syn::Result<(Vec<Expr>, Expr)> {
+    let mut seeds = Vec::new();
+    let mut program = None;
+    for expr in Punctuated::<Expr, Token![,]>::parse_terminated(input)? {
+        match expr {
+            Expr::Assign(assign) if is_ident(&assign.left, "program") => {
+                program = Some(*assign.right);
+            }
+            expr => seeds.push(expr),
+        }
+    }
+    Ok((
+        seeds,
+        program.unwrap_or_else(|| syn::parse_quote!(crate::ID)),
+    ))
+}
+
+fn is_ident(expr: &Expr, ident: &str) -> bool {
+    matches!(expr, Expr::Path(path) if path.path.is_ident(ident))
+}
@@ -0,0 +1,291 @@
+use steel::*;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum MyAccount {
+    Counter = 0,
+    Profile = 1,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(discriminator = MyAccount::Profile)]
+pub struct Profile {
+    pub id: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(discriminator = 3)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub fee: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(discriminator = 0x0102, width = 2)]
+pub struct Wide {
+    pub value: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(hash)]
+pub struct Hashed {
+    pub value: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(hash, namespace = "other")]
+pub struct Namespaced {
+    pub value: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(discriminator = MyAccount::Counter)]
+pub struct CounterV0 {
+    pub value: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Account)]
+#[account(discriminator = MyAccount::Counter, version = 1, migrate_from(CounterV0))]
+pub struct Counter {
+    pub value: u64,
+    pub limit: u64,
+}
+
+impl From<CounterV0> for Counter {
+    fn from(old: CounterV0) -> Self {
+        Counter {
+            value: old.value,
+            limit: u64::MAX,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, Instruction)]
+#[instruction(discriminator = 1)]
+pub struct Add {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, Instruction)]
+#[instruction(hash)]
+pub struct InitializeMint {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Event)]
+pub struct AddEvent {
+    pub amount: u64,
+}
+
+#[test]
+fn account_discriminator_and_size() {
+    assert_eq!(Profile::discriminator(), 1);
+    assert_eq!(Config::discriminator(), 3);
+    assert_eq!(Profile::SIZE, 16);
+    assert_eq!(Config::SIZE, 48);
+}
+
+#[test]
+fn account_deserialize() {
+    let mut data = [0u8; Profile::SIZE];
+    data[0] = 1;
+    data[8] = 42;
+    let profile = Profile::try_from_bytes(&data).unwrap();
+    assert_eq!(profile.id, 42);
+    assert_eq!(profile.to_bytes(), &data[8..]);
+
+    data[0] = 3;
+    assert!(Profile::try_from_bytes(&data).is_err());
+}
+
+#[test]
+fn account_validation() {
+    let profile = Profile { id: 7 };
+    assert!(profile.assert(|p| p.id == 7).is_ok());
+    assert_eq!(
+        profile.assert(|p| p.id == 8).unwrap_err(),
+        ProgramError::InvalidAccountData
+    );
+    assert_eq!(
+        profile
+            .assert_err(|p| p.id == 8, ProgramError::Custom(1))
+            .unwrap_err(),
+        ProgramError::Custom(1)
+    );
+    assert_eq!(
+        profile.assert_msg(|p| p.id == 8, "id").unwrap_err(),
+        ProgramError::InvalidAccountData
+    );
+
+    let mut profile = Profile { id: 7 };
+    assert_eq!(7, profile.assert_mut(|p| p.id == 7).unwrap().id);
+    assert!(profile.assert_mut_msg(|p| p.id == 8, "id").is_err());
+}
+
+#[test]
+fn account_migrate() {
+    assert_eq!(Counter::DISCRIMINATOR, &[0]);
+    assert_eq!(Counter::VERSION, 1);
+    assert_eq!(CounterV0::VERSION, 0);
+    assert_eq!(Wide::DISCRIMINATOR, &[0x02, 0x01]);
+
+    let mut data = [0u8; CounterV0::SIZE];
+    data[8] = 42;
+    let counter = Counter::migrate(0, &data).unwrap();
+    assert_eq!(counter.value, 42);
+    assert_eq!(counter.limit, u64::MAX);
+    assert!(Counter::migrate(1, &data).is_err());
+}
+
+#[test]
+fn instruction_bytes() {
+    let ix = Add {
+        amount: 5u64.to_le_bytes(),
+    };
+    let bytes = ix.to_bytes();
+    assert_eq!(bytes[0], 1);
+    assert_eq!(bytes.len(), 9);
+    let parsed = Add::try_from_bytes(&bytes[1..]).unwrap();
+    assert_eq!(u64::from_le_bytes(parsed.amount), 5);
+    assert!(Add::try_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn wide_discriminators() {
+    assert_eq!(Wide::discriminator_bytes(), &[0x02, 0x01]);
+    assert_eq!(Wide::discriminator(), 0x02);
+
+    let mut data = [0u8; Wide::SIZE];
+    data[..2].copy_from_slice(&[0x02, 0x01]);
+    data[8] = 5;
+    assert_eq!(Wide::try_from_bytes(&data).unwrap().value, 5);
+    data[1] = 0;
+    assert!(Wide::try_from_bytes(&data).is_err());
+}
+
+#[test]
+fn hashed_discriminators() {
+    let hash = solana_program::hash::hash(b"account:Hashed").to_bytes();
+    assert_eq!(Hashed::discriminator_bytes(), &hash[..8]);
+
+    let hash = solana_program::hash::hash(b"other:Namespaced").to_bytes();
+    assert_eq!(Namespaced::discriminator_bytes(), &hash[..8]);
+
+    let hash = solana_program::hash::hash(b"global:initialize_mint").to_bytes();
+    assert_eq!(InitializeMint::discriminator_bytes(), &hash[..8]);
+    assert_eq!(InitializeMint {}.to_bytes(), &hash[..8]);
+}
+
+#[test]
+fn event_bytes() {
+    let event = AddEvent { amount: 9 };
+    assert_eq!(AddEvent::from_bytes(event.to_bytes()), &event);
+}
+
+pub const PROFILE: &[u8] = b"profile";
+
+#[derive(Accounts)]
+pub struct UpdateProfile<'a, 'info> {
+    #[signer]
+    pub signer: &'a AccountInfo<'info>,
+    #[writable]
+    #[seeds(PROFILE, signer.key.as_ref(), program = crate::ID)]
+    #[account(Profile)]
+    pub profile: &'a AccountInfo<'info>,
+    #[program(system_program::ID)]
+    pub system_program: &'a AccountInfo<'info>,
+    #[remaining]
+    pub remaining: &'a [AccountInfo<'info>],
+}
+
+declare_id!("11111111111111111111111111111112");
+
+#[test]
+fn accounts_context() {
+    let signer = Pubkey::new_unique();
+    let profile = Pubkey::find_program_address(&[PROFILE, signer.as_ref()], &ID).0;
+    let system = system_program::ID;
+    let native_loader = Pubkey::new_unique();
+    let (mut l0, mut l1, mut l2) = (0u64, 0u64, 0u64);
+    let (mut d0, mut d2) = ([0u8; 0], [0u8; 0]);
+    let mut d1 = [0u8; Profile::SIZE];
+    d1[0] = MyAccount::Profile as u8;
+
+    let mut accounts = vec![
+        AccountInfo::new(&signer, true, true, &mut l0, &mut d0, &system, false, 0),
+        AccountInfo::new(&profile, false, true, &mut l1, &mut d1, &ID, false, 0),
+        AccountInfo::new(
+            &system,
+            false,
+            false,
+            &mut l2,
+            &mut d2,
+            &native_loader,
+            true,
+            0,
+        ),
+    ];
+
+    // Valid accounts.
+    let ctx = UpdateProfile::try_from(accounts.as_slice()).unwrap();
+    assert_eq!(ctx.profile.key, &profile);
+    assert!(ctx.remaining.is_empty());
+
+    // Not enough accounts.
+    assert_eq!(
+        UpdateProfile::try_from(&accounts[..2]).err(),
+        Some(ProgramError::NotEnoughAccountKeys)
+    );
+
+    // Missing signature.
+    accounts[0].is_signer = false;
+    assert_eq!(
+        UpdateProfile::try_from(accounts.as_slice()).err(),
+        Some(ProgramError::MissingRequiredSignature)
+    );
+    accounts[0].is_signer = true;
+
+    // Wrong account type.
+    accounts[1].data.borrow_mut()[0] = MyAccount::Counter as u8;
+    assert_eq!(
+        UpdateProfile::try_from(accounts.as_slice()).err(),
+        Some(ProgramError::InvalidAccountData)
+    );
+}
+
+#[derive(Accounts)]
+pub struct RemainingOnly<'a, 'info> {
+    #[remaining]
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+#[test]
+fn accounts_remaining_only() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = [];
+    let accounts = [AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &key,
+        false,
+        0,
+    )];
+    let ctx = RemainingOnly::try_from(accounts.as_slice()).unwrap();
+    assert_eq!(ctx.accounts.len(), 1);
+    assert!(RemainingOnly::try_from(&accounts[..0])
+        .unwrap()
+        .accounts
+        .is_empty());
+}
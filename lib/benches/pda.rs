@@ -0,0 +1,75 @@
+//! Compares validating PDAs with `has_seeds`, which searches for the bump, against
+//! `has_seeds_with_bump`, which derives the address once from a known bump.
+//!
+//! Compute units are counted from the cost the runtime charges per address derivation, since
+//! `create_program_address` and every bump tried by `find_program_address` cost the same. Times
+//! are measured natively on the host and only compare the two approaches. Run with
+//! `cargo bench -p steel --bench pda`.
+
+use std::{hint::black_box, time::Instant};
+
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+use steel::AccountInfoValidation;
+
+const SAMPLES: u32 = 1000;
+
+/// Compute units charged by the runtime for each address derivation.
+const DERIVATION_UNITS: u64 = 1500;
+
+/// Validates an account with the seeds and canonical bump of its address.
+type Validate<'a> = dyn Fn(&AccountInfo, &[&[u8]], u8) + 'a;
+
+fn main() {
+    let program_id = Pubkey::new_unique();
+    let accounts = (0..SAMPLES as u64)
+        .map(|i| {
+            let id = i.to_le_bytes();
+            let (pda, bump) = Pubkey::find_program_address(&[b"counter", &id], &program_id);
+            (id, pda, bump)
+        })
+        .collect::<Vec<_>>();
+
+    // Mean time per validation.
+    let owner = Pubkey::default();
+    let time = |validate: &Validate| {
+        let start = Instant::now();
+        for (id, pda, bump) in &accounts {
+            let mut lamports = 0;
+            let mut data = [];
+            let info = AccountInfo::new(
+                pda,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &owner,
+                false,
+                0,
+            );
+            validate(&info, &[b"counter", id], *bump);
+        }
+        start.elapsed() / SAMPLES
+    };
+    let search_time = time(&|info, seeds, _| {
+        black_box(info.has_seeds(seeds, &program_id)).unwrap();
+    });
+    let bump_time = time(&|info, seeds, bump| {
+        black_box(info.has_seeds_with_bump(seeds, &program_id, bump)).unwrap();
+    });
+
+    // Mean compute units per validation. Searching tries bumps from 255 down to the canonical one.
+    let search_units = accounts
+        .iter()
+        .map(|(_, _, bump)| (256 - *bump as u64) * DERIVATION_UNITS)
+        .sum::<u64>()
+        / SAMPLES as u64;
+    let bump_units = DERIVATION_UNITS;
+
+    println!("{} PDAs, mean per validation", SAMPLES);
+    println!("                    {:>10} {:>10}", "CU", "host time");
+    println!(
+        "has_seeds           {:>10} {:>10?}",
+        search_units, search_time
+    );
+    println!("has_seeds_with_bump {:>10} {:>10?}", bump_units, bump_time);
+}
@@ -1,10 +1,15 @@
 use bytemuck::Pod;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
-    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey::Pubkey,
+    rent::Rent, sysvar::Sysvar,
 };
 
-use crate::{trace, CloseAccount, Discriminator};
+use crate::{AccountInfoValidation, CloseAccount, Discriminator, LamportTransfer};
+
+use super::{
+    deserialize::{is_versioned, VERSION_OFFSET},
+    header::BUMP_OFFSET,
+};
 
 /// Invokes a CPI with provided signer seeds and program id.
 #[inline(always)]
@@ -67,6 +72,9 @@ pub fn create_account<'a, 'info>(
 }
 
 /// Creates a new program account.
+///
+/// The canonical bump is stored in the account header if the discriminator is at most 4 bytes
+/// wide, so the account can later be validated with `has_seeds_with_stored_bump`.
 #[inline(always)]
 pub fn create_program_account<'a, 'info, T: Discriminator + Pod>(
     target_account: &'a AccountInfo<'info>,
@@ -75,17 +83,21 @@ pub fn create_program_account<'a, 'info, T: Discriminator + Pod>(
     owner: &Pubkey,
     seeds: &[&[u8]],
 ) -> ProgramResult {
-    create_program_account_with_bump::<T>(
+    let bump = target_account.has_seeds_returning_bump(seeds, owner)?;
+    create_program_account_with_bump_unchecked::<T>(
         target_account,
         system_program,
         payer,
         owner,
         seeds,
-        Pubkey::find_program_address(seeds, owner).1,
+        bump,
     )
 }
 
 /// Creates a new program account with user-provided bump.
+///
+/// The bump is only checked to derive the address of the account, so callers must pass the
+/// canonical bump, such as one returned by `has_seeds_returning_bump`.
 #[inline(always)]
 pub fn create_program_account_with_bump<'a, 'info, T: Discriminator + Pod>(
     target_account: &'a AccountInfo<'info>,
@@ -94,9 +106,30 @@ pub fn create_program_account_with_bump<'a, 'info, T: Discriminator + Pod>(
     owner: &Pubkey,
     seeds: &[&[u8]],
     bump: u8,
+) -> ProgramResult {
+    target_account.has_seeds_with_bump(seeds, owner, bump)?;
+    create_program_account_with_bump_unchecked::<T>(
+        target_account,
+        system_program,
+        payer,
+        owner,
+        seeds,
+        bump,
+    )
+}
+
+/// Creates a new program account at a PDA already validated with its bump.
+#[inline(always)]
+fn create_program_account_with_bump_unchecked<'a, 'info, T: Discriminator + Pod>(
+    target_account: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
 ) -> ProgramResult {
     // Allocate space.
-    allocate_account_with_bump(
+    allocate_account_with_bump_unchecked(
         target_account,
         system_program,
         payer,
@@ -106,9 +139,14 @@ pub fn create_program_account_with_bump<'a, 'info, T: Discriminator + Pod>(
         bump,
     )?;
 
-    // Set discriminator.
+    // Set discriminator, version and bump.
+    let discriminator = T::discriminator_bytes();
     let mut data = target_account.data.borrow_mut();
-    data[0] = T::discriminator();
+    data[..discriminator.len()].copy_from_slice(discriminator);
+    if is_versioned::<T>() {
+        data[VERSION_OFFSET] = T::version();
+        data[BUMP_OFFSET] = bump;
+    }
 
     Ok(())
 }
@@ -123,18 +161,22 @@ pub fn allocate_account<'a, 'info>(
     owner: &Pubkey,
     seeds: &[&[u8]],
 ) -> ProgramResult {
-    allocate_account_with_bump(
+    let bump = target_account.has_seeds_returning_bump(seeds, owner)?;
+    allocate_account_with_bump_unchecked(
         target_account,
         system_program,
         payer,
         space,
         owner,
         seeds,
-        Pubkey::find_program_address(seeds, owner).1,
+        bump,
     )
 }
 
 /// Allocates space for a new program account with user-provided bump.
+///
+/// The bump is only checked to derive the address of the account, so callers must pass the
+/// canonical bump, such as one returned by `has_seeds_returning_bump`.
 #[inline(always)]
 pub fn allocate_account_with_bump<'a, 'info>(
     target_account: &'a AccountInfo<'info>,
@@ -145,26 +187,56 @@ pub fn allocate_account_with_bump<'a, 'info>(
     seeds: &[&[u8]],
     bump: u8,
 ) -> ProgramResult {
-    // Validate seeds.
-    let pda = Pubkey::find_program_address(seeds, owner);
-    if target_account.key.ne(&pda.0) {
-        return Err(trace(
-            format!(
-                "Account has invalid seeds {} != {}",
-                target_account.key, pda.0
-            )
-            .as_str(),
-            ProgramError::InvalidSeeds,
-        ));
-    }
+    target_account.has_seeds_with_bump(seeds, owner, bump)?;
+    allocate_account_with_bump_unchecked(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        seeds,
+        bump,
+    )
+}
 
+/// Allocates space for a new program account at a PDA already validated with its bump.
+#[inline(always)]
+fn allocate_account_with_bump_unchecked<'a, 'info>(
+    target_account: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
     // Combine seeds
     let bump: &[u8] = &[bump];
     let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
     combined_seeds.extend_from_slice(seeds);
     combined_seeds.push(bump);
-    let seeds = combined_seeds.as_slice();
 
+    allocate_account_signed(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        &combined_seeds,
+    )
+}
+
+/// Allocates space for a new account owned by `owner`, signing for it with seeds that already
+/// include the bump.
+#[inline(always)]
+pub(crate) fn allocate_account_signed<'a, 'info>(
+    target_account: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
     // Allocate space for account
     let rent = Rent::get()?;
     if target_account.lamports().eq(&0) {
@@ -224,6 +296,49 @@ pub fn allocate_account_with_bump<'a, 'info>(
     Ok(())
 }
 
+/// Resizes a program account.
+///
+/// If the account grows, the payer tops up the lamports needed for rent exemption. If it shrinks,
+/// the rent no longer needed is refunded to the payer. Any new bytes are zero-initialized.
+#[inline(always)]
+pub fn resize_account<'a, 'info>(
+    target_account: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    new_size: usize,
+) -> ProgramResult {
+    // Realloc data.
+    let old_size = target_account.data_len();
+    target_account.realloc(new_size, true)?;
+
+    // Rebalance rent.
+    let rent = Rent::get()?;
+    let lamports = target_account.lamports();
+    let rent_exempt_balance = rent.minimum_balance(new_size);
+    if rent_exempt_balance.gt(&lamports) {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                payer.key,
+                target_account.key,
+                rent_exempt_balance - lamports,
+            ),
+            &[
+                payer.clone(),
+                target_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    } else if new_size.lt(&old_size) {
+        let refund = rent
+            .minimum_balance(old_size)
+            .saturating_sub(rent_exempt_balance)
+            .min(lamports - rent_exempt_balance);
+        target_account.send(refund, payer);
+    }
+
+    Ok(())
+}
+
 /// Closes an account and returns the remaining rent lamports to the provided recipient.
 #[inline(always)]
 pub fn close_account<'info>(
@@ -232,3 +347,58 @@ pub fn close_account<'info>(
 ) -> ProgramResult {
     account_info.close(recipient)
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_program::{program_error::ProgramError, system_instruction, system_program};
+
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_allocate_account_with_bump() {
+        test_utils::setup();
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"counter"];
+        let (pda, bump) = Pubkey::find_program_address(seeds, &program_id);
+        let mut lamports = 0;
+        let mut data = [];
+        let info = AccountInfo::new(
+            &pda,
+            true,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program::ID,
+            false,
+            0,
+        );
+
+        // The bump must derive the address of the account.
+        assert_eq!(
+            allocate_account_with_bump(
+                &info,
+                &info,
+                &info,
+                16,
+                &program_id,
+                seeds,
+                bump.wrapping_sub(1)
+            ),
+            Err(ProgramError::InvalidSeeds)
+        );
+        assert!(test_utils::invoked().is_empty());
+
+        allocate_account_with_bump(&info, &info, &info, 16, &program_id, seeds, bump).unwrap();
+        assert_eq!(
+            vec![system_instruction::create_account(
+                &pda,
+                &pda,
+                Rent::default().minimum_balance(16),
+                16,
+                &program_id
+            )],
+            test_utils::invoked()
+        );
+    }
+}
@@ -1,8 +1,49 @@
-use bytemuck::Pod;
+use bytemuck::{Pod, PodCastError};
 use solana_program::program_error::ProgramError;
 
+use crate::trace;
+
+/// The offset of the version byte in the 8 byte account header.
+pub(crate) const VERSION_OFFSET: usize = 4;
+
+/// Identifies an account or instruction type.
+///
+/// Accounts start with an 8 byte header. The discriminator is written at the start of the header,
+/// followed by the account version at byte 4 if the discriminator is at most 4 bytes wide.
 pub trait Discriminator {
+    /// Returns the first byte of the discriminator.
     fn discriminator() -> u8;
+
+    /// Returns the full discriminator, which is 1, 2, 4 or 8 bytes wide.
+    ///
+    /// Defaults to the single byte returned by `discriminator()`. Wider discriminators are
+    /// written at the start of the 8 byte account header or instruction data.
+    fn discriminator_bytes() -> &'static [u8] {
+        static BYTES: [u8; 256] = {
+            let mut bytes = [0u8; 256];
+            let mut i = 0;
+            while i < 256 {
+                bytes[i] = i as u8;
+                i += 1;
+            }
+            bytes
+        };
+        let i = Self::discriminator() as usize;
+        &BYTES[i..i + 1]
+    }
+
+    /// Returns the version of the account layout. Defaults to 0.
+    ///
+    /// Accounts with a discriminator wider than 4 bytes have no version byte and are always
+    /// version 0.
+    fn version() -> u8 {
+        0
+    }
+}
+
+/// Returns whether accounts of type `T` store a version byte in their header.
+pub(crate) fn is_versioned<T: Discriminator>() -> bool {
+    T::discriminator_bytes().len() <= VERSION_OFFSET
 }
 
 pub trait AccountDeserialize {
@@ -14,22 +55,16 @@ impl<T> AccountDeserialize for T
 where
     T: Discriminator + Pod,
 {
+    #[track_caller]
     fn try_from_bytes(data: &[u8]) -> Result<&Self, ProgramError> {
-        if Self::discriminator().ne(&data[0]) {
-            return Err(solana_program::program_error::ProgramError::InvalidAccountData);
-        }
-        bytemuck::try_from_bytes::<Self>(&data[8..]).or(Err(
-            solana_program::program_error::ProgramError::InvalidAccountData,
-        ))
+        check_account_data::<T>(data, account_size::<T>(), true)?;
+        bytemuck::try_from_bytes::<Self>(&data[8..]).map_err(cast_error::<T>)
     }
 
+    #[track_caller]
     fn try_from_bytes_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if Self::discriminator().ne(&data[0]) {
-            return Err(solana_program::program_error::ProgramError::InvalidAccountData);
-        }
-        bytemuck::try_from_bytes_mut::<Self>(&mut data[8..]).or(Err(
-            solana_program::program_error::ProgramError::InvalidAccountData,
-        ))
+        check_account_data::<T>(data, account_size::<T>(), true)?;
+        bytemuck::try_from_bytes_mut::<Self>(&mut data[8..]).map_err(cast_error::<T>)
     }
 }
 
@@ -42,34 +77,145 @@ where
 pub trait AccountHeaderDeserialize {
     fn try_header_from_bytes(data: &[u8]) -> Result<(&Self, &[u8]), ProgramError>;
     fn try_header_from_bytes_mut(data: &mut [u8]) -> Result<(&mut Self, &mut [u8]), ProgramError>;
+
+    /// Parses the header and casts the remaining bytes to a slice of `E` elements.
+    fn try_header_with_tail_from_bytes<E: Pod>(data: &[u8]) -> Result<(&Self, &[E]), ProgramError>;
+
+    /// Parses the header and casts the remaining bytes to a mutable slice of `E` elements.
+    fn try_header_with_tail_from_bytes_mut<E: Pod>(
+        data: &mut [u8],
+    ) -> Result<(&mut Self, &mut [E]), ProgramError>;
 }
 
 impl<T> AccountHeaderDeserialize for T
 where
     T: Discriminator + Pod,
 {
+    #[track_caller]
     fn try_header_from_bytes(data: &[u8]) -> Result<(&Self, &[u8]), ProgramError> {
-        if Self::discriminator().ne(&data[0]) {
-            return Err(solana_program::program_error::ProgramError::InvalidAccountData);
-        }
+        check_account_data::<T>(data, account_size::<T>(), false)?;
         let (prefix, remainder) = data[8..].split_at(std::mem::size_of::<T>());
         Ok((
-            bytemuck::try_from_bytes::<Self>(prefix).or(Err(
-                solana_program::program_error::ProgramError::InvalidAccountData,
-            ))?,
+            bytemuck::try_from_bytes::<Self>(prefix).map_err(cast_error::<T>)?,
             remainder,
         ))
     }
 
+    #[track_caller]
     fn try_header_from_bytes_mut(data: &mut [u8]) -> Result<(&mut Self, &mut [u8]), ProgramError> {
+        check_account_data::<T>(data, account_size::<T>(), false)?;
         let (prefix, remainder) = data[8..].split_at_mut(std::mem::size_of::<T>());
         Ok((
-            bytemuck::try_from_bytes_mut::<Self>(prefix).or(Err(
-                solana_program::program_error::ProgramError::InvalidAccountData,
-            ))?,
+            bytemuck::try_from_bytes_mut::<Self>(prefix).map_err(cast_error::<T>)?,
             remainder,
         ))
     }
+
+    #[track_caller]
+    fn try_header_with_tail_from_bytes<E: Pod>(data: &[u8]) -> Result<(&Self, &[E]), ProgramError> {
+        let (header, tail) = Self::try_header_from_bytes(data)?;
+        Ok((
+            header,
+            bytemuck::try_cast_slice::<u8, E>(tail).map_err(tail_cast_error::<T, E>)?,
+        ))
+    }
+
+    #[track_caller]
+    fn try_header_with_tail_from_bytes_mut<E: Pod>(
+        data: &mut [u8],
+    ) -> Result<(&mut Self, &mut [E]), ProgramError> {
+        let (header, tail) = Self::try_header_from_bytes_mut(data)?;
+        Ok((
+            header,
+            bytemuck::try_cast_slice_mut::<u8, E>(tail).map_err(tail_cast_error::<T, E>)?,
+        ))
+    }
+}
+
+/// Returns the size of an account of type `T`, including the 8 byte discriminator header.
+const fn account_size<T>() -> usize {
+    8 + std::mem::size_of::<T>()
+}
+
+/// Checks the account data is long enough for type `T`, or exactly as long if `exact` is set,
+/// and starts with its discriminator and version.
+#[track_caller]
+fn check_account_data<T: Discriminator>(
+    data: &[u8],
+    size: usize,
+    exact: bool,
+) -> Result<(), ProgramError> {
+    if data.len() < size || (exact && data.len() != size) {
+        return Err(trace(
+            format!(
+                "{} account data has invalid length {}, expected {}{} bytes",
+                std::any::type_name::<T>(),
+                data.len(),
+                if exact { "" } else { "at least " },
+                size
+            )
+            .as_str(),
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    if !data.starts_with(T::discriminator_bytes()) {
+        return Err(trace(
+            format!(
+                "{} account has invalid discriminator {:?}, expected {:?}",
+                std::any::type_name::<T>(),
+                &data[..T::discriminator_bytes().len()],
+                T::discriminator_bytes()
+            )
+            .as_str(),
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    if is_versioned::<T>() && data[VERSION_OFFSET] != T::version() {
+        return Err(trace(
+            format!(
+                "{} account has version {}, expected {}",
+                std::any::type_name::<T>(),
+                data[VERSION_OFFSET],
+                T::version()
+            )
+            .as_str(),
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    Ok(())
+}
+
+#[track_caller]
+fn cast_error<T>(err: PodCastError) -> ProgramError {
+    trace(
+        format!(
+            "{} account data is invalid: {:?}",
+            std::any::type_name::<T>(),
+            err
+        )
+        .as_str(),
+        ProgramError::InvalidAccountData,
+    )
+}
+
+#[track_caller]
+fn tail_cast_error<T, E>(err: PodCastError) -> ProgramError {
+    trace(
+        format!(
+            "{} account tail is not a slice of {} ({} bytes each): {:?}",
+            std::any::type_name::<T>(),
+            std::any::type_name::<E>(),
+            std::mem::size_of::<E>(),
+            err
+        )
+        .as_str(),
+        ProgramError::InvalidAccountData,
+    )
+}
+
+/// Returns the size of an account with a header of type `H` followed by `len` elements of type `E`.
+pub const fn account_size_with_tail<H: Pod, E: Pod>(len: usize) -> usize {
+    account_size::<H>() + len * std::mem::size_of::<E>()
 }
 
 #[cfg(test)]
@@ -78,6 +224,7 @@ mod tests {
 
     use super::*;
     use bytemuck::{Pod, Zeroable};
+    use proptest::prelude::*;
 
     #[repr(C)]
     #[derive(Copy, Clone)]
@@ -117,6 +264,41 @@ mod tests {
         assert_eq!(5, foo.field[0]);
     }
 
+    #[test]
+    fn account_headers_with_tail() {
+        // Use a u64 buffer so the tail is aligned.
+        let mut buffer = [0u64; 5];
+        let data = bytemuck::cast_slice_mut::<u64, u8>(&mut buffer);
+        data[8] = 2;
+        data[16] = 5;
+        data[24] = 6;
+        let (header, tail) =
+            GenericallySizedTypeHeader::try_header_with_tail_from_bytes::<u64>(data).unwrap();
+        assert_eq!(2, header.field_len);
+        assert_eq!(&[5, 6, 0], tail);
+
+        let (header, tail) =
+            GenericallySizedTypeHeader::try_header_with_tail_from_bytes_mut::<u64>(data).unwrap();
+        header.field_len = 3;
+        tail[2] = 7;
+        assert_eq!(3, data[8]);
+        assert_eq!(7, data[32]);
+
+        // The tail must be a whole number of elements.
+        assert!(
+            GenericallySizedTypeHeader::try_header_with_tail_from_bytes::<u64>(&data[..36])
+                .is_err()
+        );
+        assert!(
+            GenericallySizedTypeHeader::try_header_with_tail_from_bytes::<u64>(&data[..12])
+                .is_err()
+        );
+        assert_eq!(
+            40,
+            account_size_with_tail::<GenericallySizedTypeHeader, u64>(3)
+        );
+    }
+
     #[repr(C)]
     #[derive(Copy, Clone, Zeroable, Pod)]
     struct TestType {
@@ -140,4 +322,140 @@ mod tests {
         assert_eq!(42, foo.field0);
         assert_eq!(43, foo.field1);
     }
+
+    #[repr(C)]
+    #[derive(Copy, Clone, Zeroable, Pod)]
+    struct WideType {
+        field0: u64,
+    }
+
+    impl Discriminator for WideType {
+        fn discriminator() -> u8 {
+            0x01
+        }
+
+        fn discriminator_bytes() -> &'static [u8] {
+            &[0x01, 0x02, 0x03, 0x04]
+        }
+    }
+
+    #[test]
+    fn account_deserialize_wide_discriminator() {
+        let mut data = [0u8; 16];
+        data[..4].copy_from_slice(&[0x01, 0x02, 0x03, 0x04]);
+        data[8] = 42;
+        assert_eq!(42, WideType::try_from_bytes(&data).unwrap().field0);
+
+        data[3] = 0;
+        assert!(WideType::try_from_bytes(&data).is_err());
+        assert_eq!(TestType::discriminator_bytes(), &[7]);
+    }
+
+    #[test]
+    fn account_deserialize_checks_version() {
+        let mut data = [0u8; 24];
+        data[0] = 7;
+        data[VERSION_OFFSET] = 1;
+        assert_eq!(
+            TestType::try_from_bytes(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert!(TestType::try_header_from_bytes(&data).is_err());
+
+        // 4 byte discriminators are directly followed by the version.
+        let mut data = [0u8; 16];
+        data[..VERSION_OFFSET].copy_from_slice(&[0x01, 0x02, 0x03, 0x04]);
+        data[VERSION_OFFSET + 1] = 1;
+        assert!(WideType::try_from_bytes(&data).is_ok());
+        data[VERSION_OFFSET] = 1;
+        assert!(WideType::try_from_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn account_deserialize_short_data() {
+        let mut data = [7u8, 0, 0, 0];
+        assert_eq!(
+            TestType::try_from_bytes(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert!(TestType::try_from_bytes_mut(&mut data).is_err());
+        assert!(TestType::try_header_from_bytes(&[]).is_err());
+        assert!(TestType::try_header_from_bytes_mut(&mut data).is_err());
+        assert!(TestType::try_header_with_tail_from_bytes::<u64>(&data).is_err());
+        assert!(WideType::try_from_bytes(&[1, 2]).is_err());
+    }
+
+    #[test]
+    fn account_header_mut_checks_discriminator() {
+        let mut buffer = [0u64; 3];
+        let data = bytemuck::cast_slice_mut::<u64, u8>(&mut buffer);
+        data[0] = 7;
+        assert!(TestType::try_header_from_bytes_mut(data).is_ok());
+        data[0] = 8;
+        assert!(TestType::try_header_from_bytes_mut(data).is_err());
+        assert!(TestType::try_header_with_tail_from_bytes_mut::<u64>(data).is_err());
+    }
+
+    /// Copies `bytes` into a buffer aligned for `u64` fields.
+    fn aligned(bytes: &[u8]) -> Vec<u64> {
+        let mut buffer = vec![0u64; bytes.len().div_ceil(8)];
+        bytemuck::cast_slice_mut::<u64, u8>(&mut buffer)[..bytes.len()].copy_from_slice(bytes);
+        buffer
+    }
+
+    proptest! {
+        #[test]
+        fn prop_deserialize_never_panics(mut bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            let _ = TestType::try_from_bytes(&bytes);
+            let _ = TestType::try_from_bytes_mut(&mut bytes);
+            let _ = WideType::try_from_bytes(&bytes);
+            let _ = TestType::try_header_from_bytes(&bytes);
+            let _ = TestType::try_header_from_bytes_mut(&mut bytes);
+            let _ = TestType::try_header_with_tail_from_bytes::<u64>(&bytes);
+            let _ = TestType::try_header_with_tail_from_bytes_mut::<u64>(&mut bytes);
+        }
+
+        #[test]
+        fn prop_deserialize_validates(
+            len in 0usize..64,
+            valid_discriminator in any::<bool>(),
+            seed in any::<u8>(),
+        ) {
+            let mut bytes = vec![seed; len];
+            if let Some(first) = bytes.first_mut() {
+                *first = if valid_discriminator { 7 } else { 8 };
+            }
+            if let Some(version) = bytes.get_mut(VERSION_OFFSET) {
+                *version = 0;
+            }
+            let mut buffer = aligned(&bytes);
+            let data = &mut bytemuck::cast_slice_mut::<u64, u8>(&mut buffer)[..len];
+            let discriminator = valid_discriminator && len > 0;
+
+            prop_assert_eq!(
+                TestType::try_from_bytes(data).is_ok(),
+                discriminator && len == 24
+            );
+            prop_assert_eq!(
+                TestType::try_from_bytes_mut(data).is_ok(),
+                discriminator && len == 24
+            );
+            prop_assert_eq!(
+                TestType::try_header_from_bytes(data).is_ok(),
+                discriminator && len >= 24
+            );
+            prop_assert_eq!(
+                TestType::try_header_from_bytes_mut(data).is_ok(),
+                discriminator && len >= 24
+            );
+            prop_assert_eq!(
+                TestType::try_header_with_tail_from_bytes::<u64>(data).is_ok(),
+                discriminator && len >= 24 && len % 8 == 0
+            );
+            prop_assert_eq!(
+                TestType::try_header_with_tail_from_bytes_mut::<u64>(data).is_ok(),
+                discriminator && len >= 24 && len % 8 == 0
+            );
+        }
+    }
 }
@@ -0,0 +1,239 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{trace, AccountInfoValidation, Discriminator};
+
+use super::deserialize::is_versioned;
+
+/// The offset of the PDA bump in the 8 byte account header.
+pub(crate) const BUMP_OFFSET: usize = 5;
+
+/// The 8 byte header at the start of every program account.
+///
+/// Accounts with a discriminator of at most 4 bytes store metadata in the rest of the header.
+/// Wider discriminators fill the whole header, so their header fields should not be used.
+///
+/// | Bytes | Field                                  |
+/// |-------|----------------------------------------|
+/// | 0..4  | discriminator, zero padded             |
+/// | 4     | layout version                         |
+/// | 5     | PDA bump, or 0 if not stored           |
+/// | 6     | flags, such as `AccountHeader::FROZEN` |
+/// | 7     | reserved                               |
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct AccountHeader {
+    pub discriminator: [u8; 4],
+    pub version: u8,
+    pub bump: u8,
+    pub flags: u8,
+    pub reserved: u8,
+}
+
+const _: () = assert!(std::mem::size_of::<AccountHeader>() == 8);
+
+impl AccountHeader {
+    /// Marks an account as frozen by the program.
+    pub const FROZEN: u8 = 1 << 0;
+
+    /// Marks an account as closed by the program.
+    pub const CLOSED: u8 = 1 << 1;
+
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags & flag == flag
+    }
+
+    pub fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.has_flag(Self::FROZEN)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.has_flag(Self::CLOSED)
+    }
+}
+
+/// Performs:
+/// 1. Program owner check
+/// 2. Account data length check
+/// 3. Conversion of the first 8 bytes of account data to &AccountHeader or &mut AccountHeader.
+pub trait AsAccountHeader {
+    fn as_account_header(&self, program_id: &Pubkey) -> Result<&AccountHeader, ProgramError>;
+
+    #[allow(clippy::mut_from_ref)]
+    fn as_account_header_mut(
+        &self,
+        program_id: &Pubkey,
+    ) -> Result<&mut AccountHeader, ProgramError>;
+
+    /// Checks the account is a `T` at a PDA of the seeds using the bump stored in its header, which
+    /// avoids the cost of `find_program_address`.
+    ///
+    /// Returns an error if `T` has no header fields or the account has no stored bump. Use
+    /// `has_seeds` for accounts created without one.
+    fn has_seeds_with_stored_bump<T: Discriminator>(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError>;
+}
+
+impl AsAccountHeader for AccountInfo<'_> {
+    #[track_caller]
+    fn as_account_header(&self, program_id: &Pubkey) -> Result<&AccountHeader, ProgramError> {
+        unsafe {
+            // Validate account owner.
+            self.has_owner(program_id)?;
+
+            // Validate account data length.
+            let data = self.try_borrow_data()?;
+            check_header_len(data.len())?;
+
+            // The header has an alignment of 1, so the cast cannot fail.
+            Ok(bytemuck::from_bytes(std::slice::from_raw_parts(
+                data.as_ptr(),
+                std::mem::size_of::<AccountHeader>(),
+            )))
+        }
+    }
+
+    #[track_caller]
+    fn as_account_header_mut(
+        &self,
+        program_id: &Pubkey,
+    ) -> Result<&mut AccountHeader, ProgramError> {
+        unsafe {
+            // Validate account owner.
+            self.has_owner(program_id)?;
+
+            // Validate account data length.
+            let mut data = self.try_borrow_mut_data()?;
+            check_header_len(data.len())?;
+
+            // The header has an alignment of 1, so the cast cannot fail.
+            Ok(bytemuck::from_bytes_mut(std::slice::from_raw_parts_mut(
+                data.as_mut_ptr(),
+                std::mem::size_of::<AccountHeader>(),
+            )))
+        }
+    }
+
+    #[track_caller]
+    fn has_seeds_with_stored_bump<T: Discriminator>(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError> {
+        if !is_versioned::<T>() {
+            return Err(trace(
+                "Accounts with a discriminator wider than 4 bytes have no stored bump",
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        self.is_type::<T>(program_id)?;
+        match self.as_account_header(program_id)?.bump {
+            0 => Err(trace(
+                "Account has no stored bump",
+                ProgramError::InvalidAccountData,
+            )),
+            bump => self.has_seeds_with_bump(seeds, program_id, bump),
+        }
+    }
+}
+
+#[track_caller]
+fn check_header_len(len: usize) -> Result<(), ProgramError> {
+    if len < std::mem::size_of::<AccountHeader>() {
+        return Err(trace(
+            format!("Account data is too short for a header {} < 8", len).as_str(),
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::deserialize::VERSION_OFFSET;
+
+    struct Counter;
+
+    impl Discriminator for Counter {
+        fn discriminator() -> u8 {
+            3
+        }
+    }
+
+    struct Hashed;
+
+    impl Discriminator for Hashed {
+        fn discriminator() -> u8 {
+            3
+        }
+
+        fn discriminator_bytes() -> &'static [u8] {
+            &[3, 0, 0, 0, 0, 0, 0, 0]
+        }
+    }
+
+    #[test]
+    fn test_account_header() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"counter"];
+        let (pda, bump) = Pubkey::find_program_address(seeds, &program_id);
+        let mut lamports = 0;
+        let mut data = [0u8; 16];
+        data[0] = 3;
+        data[VERSION_OFFSET] = 1;
+        data[BUMP_OFFSET] = bump;
+        let info = AccountInfo::new(
+            &pda,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let header = info.as_account_header(&program_id).unwrap();
+        assert_eq!([3, 0, 0, 0], header.discriminator);
+        assert_eq!(1, header.version);
+        assert_eq!(bump, header.bump);
+        assert!(!header.is_frozen());
+        assert!(info
+            .has_seeds_with_stored_bump::<Counter>(seeds, &program_id)
+            .is_ok());
+        assert!(info
+            .has_seeds_with_stored_bump::<Counter>(&[b"other"], &program_id)
+            .is_err());
+
+        // Types without header fields have no stored bump.
+        assert!(info
+            .has_seeds_with_stored_bump::<Hashed>(seeds, &program_id)
+            .is_err());
+        assert!(info.as_account_header(&Pubkey::new_unique()).is_err());
+
+        let header = info.as_account_header_mut(&program_id).unwrap();
+        header.set_flag(AccountHeader::FROZEN, true);
+        header.bump = 0;
+        assert_eq!(AccountHeader::FROZEN, info.try_borrow_data().unwrap()[6]);
+
+        // Accounts without a stored bump are rejected rather than searched.
+        assert_eq!(
+            info.has_seeds_with_stored_bump::<Counter>(seeds, &program_id)
+                .err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert!(info.has_seeds(seeds, &program_id).is_ok());
+    }
+}
@@ -0,0 +1,265 @@
+use bytemuck::Pod;
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{resize_account, trace, AccountInfoValidation, AsAccount, Discriminator};
+
+use super::deserialize::{is_versioned, VERSION_OFFSET};
+
+/// Converts accounts written by prior versions of a type to the current version.
+///
+/// Implemented by `#[derive(Account)]` for accounts declared with
+/// `#[account(version = N, migrate_from(..))]`.
+pub trait AccountMigrate: Discriminator + Pod {
+    /// Converts the full data of an account with the given version, including its header.
+    fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError>;
+}
+
+/// Performs:
+/// 1. Program owner, writable and discriminator checks
+/// 2. Migration of accounts written by prior versions of `T`
+/// 3. Checked bytemuck conversion of account data to &mut T.
+pub trait AsAccountMigrating<'info> {
+    /// Parses an account, first migrating it in place if it has an older version.
+    ///
+    /// Migrated accounts are reallocated to the size of `T`. The payer tops up the rent needed, or
+    /// is refunded the rent no longer needed if the account shrinks.
+    #[allow(clippy::mut_from_ref)]
+    fn as_account_migrating<T: AccountMigrate>(
+        &self,
+        program_id: &Pubkey,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<&mut T, ProgramError>;
+}
+
+impl<'info> AsAccountMigrating<'info> for AccountInfo<'info> {
+    #[track_caller]
+    fn as_account_migrating<T: AccountMigrate>(
+        &self,
+        program_id: &Pubkey,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<&mut T, ProgramError> {
+        if !is_versioned::<T>() {
+            return self.as_account_mut::<T>(program_id);
+        }
+
+        // Read the account version.
+        self.is_writable()?.is_type::<T>(program_id)?;
+        let version = *self.try_borrow_data()?.get(VERSION_OFFSET).ok_or_else(|| {
+            trace(
+                "Account data is too short for a header",
+                ProgramError::InvalidAccountData,
+            )
+        })?;
+        if version == T::version() {
+            return self.as_account_mut::<T>(program_id);
+        }
+        if version > T::version() {
+            return Err(trace(
+                format!("Account version {} is newer than {}", version, T::version()).as_str(),
+                ProgramError::InvalidAccountData,
+            ));
+        }
+
+        // Convert the account data and rewrite it in place.
+        let account = T::migrate(version, &self.try_borrow_data()?)?;
+        resize_account(self, payer, system_program, 8 + std::mem::size_of::<T>())?;
+        {
+            let mut data = self.try_borrow_mut_data()?;
+            data[VERSION_OFFSET] = T::version();
+            data[8..].copy_from_slice(bytemuck::bytes_of(&account));
+        }
+
+        self.as_account_mut::<T>(program_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::{Pod, Zeroable};
+    use solana_program::{rent::Rent, system_instruction, system_program};
+
+    use crate::{test_utils, AccountDeserialize};
+
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+    struct CounterV0 {
+        value: u64,
+    }
+
+    impl Discriminator for CounterV0 {
+        fn discriminator() -> u8 {
+            3
+        }
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+    struct Counter {
+        value: u64,
+        limit: u64,
+    }
+
+    impl Discriminator for Counter {
+        fn discriminator() -> u8 {
+            3
+        }
+
+        fn version() -> u8 {
+            1
+        }
+    }
+
+    impl From<CounterV0> for Counter {
+        fn from(old: CounterV0) -> Self {
+            Counter {
+                value: old.value,
+                limit: u64::MAX,
+            }
+        }
+    }
+
+    impl AccountMigrate for Counter {
+        fn migrate(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+            match version {
+                0 => Ok((*CounterV0::try_from_bytes(data)?).into()),
+                _ => Err(ProgramError::InvalidAccountData),
+            }
+        }
+    }
+
+    #[test]
+    fn test_account_migrate() {
+        let mut buffer = [0u64; 3];
+        let data = bytemuck::cast_slice_mut::<u64, u8>(&mut buffer);
+        data[0] = 3;
+        data[8] = 42;
+
+        // Accounts are rejected by the current version until they are migrated.
+        assert!(Counter::try_from_bytes(data).is_err());
+        let counter = Counter::migrate(0, &data[..16]).unwrap();
+        assert_eq!(42, counter.value);
+        assert_eq!(u64::MAX, counter.limit);
+
+        data[VERSION_OFFSET] = 1;
+        assert!(Counter::try_from_bytes(data).is_ok());
+        assert!(CounterV0::try_from_bytes(&data[..16]).is_err());
+        assert!(Counter::migrate(1, data).is_err());
+    }
+
+    #[test]
+    fn test_as_account_migrating_current() {
+        let key = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut buffer = [0u64; 3];
+        let data = bytemuck::cast_slice_mut::<u64, u8>(&mut buffer);
+        data[0] = 3;
+        data[VERSION_OFFSET] = 1;
+        data[8] = 42;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Accounts at the current version are parsed without a migration.
+        let counter = info
+            .as_account_migrating::<Counter>(&program_id, &info, &info)
+            .unwrap();
+        assert_eq!(42, counter.value);
+
+        // Accounts from newer versions are rejected.
+        info.try_borrow_mut_data().unwrap()[VERSION_OFFSET] = 2;
+        assert!(info
+            .as_account_migrating::<Counter>(&program_id, &info, &info)
+            .is_err());
+    }
+
+    /// A key preceded by the original data length, as serialized by the runtime for `realloc`.
+    #[repr(C)]
+    struct SerializedKey {
+        padding: u32,
+        original_data_len: u32,
+        key: Pubkey,
+    }
+
+    #[test]
+    fn test_as_account_migrating() {
+        test_utils::setup();
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let old_size = 8 + std::mem::size_of::<CounterV0>();
+        let new_size = 8 + std::mem::size_of::<Counter>();
+        let key = SerializedKey {
+            padding: 0,
+            original_data_len: old_size as u32,
+            key: Pubkey::new_unique(),
+        };
+
+        // The data is preceded by its length and followed by room to grow.
+        let mut buffer = [0u64; 4];
+        let bytes = bytemuck::cast_slice_mut::<u64, u8>(&mut buffer);
+        bytes[..8].copy_from_slice(&(old_size as u64).to_le_bytes());
+        bytes[8] = 3;
+        bytes[16] = 42;
+        let data = unsafe { std::slice::from_raw_parts_mut(bytes.as_mut_ptr().add(8), old_size) };
+        let mut lamports = Rent::default().minimum_balance(old_size);
+        let info = AccountInfo::new(
+            &key.key,
+            false,
+            true,
+            &mut lamports,
+            data,
+            &program_id,
+            false,
+            0,
+        );
+        let mut payer_lamports = 1_000_000_000;
+        let mut payer_data = [];
+        let payer_info = AccountInfo::new(
+            &payer,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+
+        // The account is migrated, grown and topped up to the rent exempt balance.
+        let counter = info
+            .as_account_migrating::<Counter>(&program_id, &payer_info, &payer_info)
+            .unwrap();
+        assert_eq!(42, counter.value);
+        assert_eq!(u64::MAX, counter.limit);
+        assert_eq!(new_size, info.data_len());
+        assert_eq!(1, info.try_borrow_data().unwrap()[VERSION_OFFSET]);
+        let top_up =
+            Rent::default().minimum_balance(new_size) - Rent::default().minimum_balance(old_size);
+        assert_eq!(Rent::default().minimum_balance(new_size), info.lamports());
+        assert_eq!(1_000_000_000 - top_up, payer_info.lamports());
+        assert_eq!(
+            vec![system_instruction::transfer(&payer, &key.key, top_up)],
+            test_utils::invoked()
+        );
+
+        // Migrated accounts parse at the current version.
+        assert!(Counter::try_from_bytes(&info.try_borrow_data().unwrap()).is_ok());
+        assert_eq!(
+            42,
+            info.as_account_migrating::<Counter>(&program_id, &payer_info, &payer_info)
+                .unwrap()
+                .value
+        );
+    }
+}
@@ -1,11 +1,17 @@
 mod close;
 mod cpi;
 mod deserialize;
+mod header;
 mod lamports;
+mod migrate;
+mod pda;
 mod validation;
 
 pub use close::*;
 pub use cpi::*;
 pub use deserialize::*;
+pub use header::*;
 pub use lamports::*;
+pub use migrate::*;
+pub use pda::*;
 pub use validation::*;
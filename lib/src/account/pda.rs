@@ -0,0 +1,260 @@
+use bytemuck::Pod;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEEDS},
+};
+
+use crate::{
+    create_program_account_with_bump, invoke_signed_with_bump, AccountInfoValidation, Discriminator,
+};
+
+/// A program derived address with typed seeds.
+///
+/// Implemented by `#[derive(Pda)]`, which declares the seeds of the address once so the client,
+/// validation and signing all use the same seeds.
+///
+/// ```ignore
+/// #[derive(Pda)]
+/// #[pda(COUNTER, authority, id)]
+/// pub struct CounterPda {
+///     pub authority: Pubkey,
+///     pub id: u64,
+/// }
+///
+/// let bump = CounterPda { authority, id }.validate(counter_info)?;
+/// ```
+pub trait Pda {
+    /// The program the address is derived from.
+    const PROGRAM_ID: Pubkey;
+
+    /// Returns the seeds of the address, excluding the bump.
+    fn seeds(&self) -> PdaSeeds<'_>;
+
+    /// Finds the address and its canonical bump.
+    fn find(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&self.seeds(), &Self::PROGRAM_ID)
+    }
+
+    /// Returns the address.
+    fn address(&self) -> Pubkey {
+        self.find().0
+    }
+
+    /// Returns the seeds of the address followed by the bump, for signing with `invoke_signed`.
+    fn signer_seeds<'a>(&'a self, bump: &'a [u8; 1]) -> PdaSeeds<'a> {
+        self.seeds().with(bump)
+    }
+
+    /// Checks the account has the address and returns its canonical bump.
+    #[track_caller]
+    fn validate(&self, account_info: &AccountInfo) -> Result<u8, ProgramError> {
+        account_info.has_seeds_returning_bump(&self.seeds(), &Self::PROGRAM_ID)
+    }
+
+    /// Checks the account has the address derived with a known bump.
+    #[track_caller]
+    fn validate_with_bump(&self, account_info: &AccountInfo, bump: u8) -> ProgramResult {
+        account_info.has_seeds_with_bump(&self.seeds(), &Self::PROGRAM_ID, bump)?;
+        Ok(())
+    }
+
+    /// Creates a program account of type `T` at the address.
+    ///
+    /// Only the address derived from the bump is checked, so it must be the canonical bump
+    /// returned by `find` or `validate`.
+    fn create_account<'a, 'info, T: Discriminator + Pod>(
+        &self,
+        target_account: &'a AccountInfo<'info>,
+        system_program: &'a AccountInfo<'info>,
+        payer: &'a AccountInfo<'info>,
+        bump: u8,
+    ) -> ProgramResult {
+        create_program_account_with_bump::<T>(
+            target_account,
+            system_program,
+            payer,
+            &Self::PROGRAM_ID,
+            &self.seeds(),
+            bump,
+        )
+    }
+
+    /// Invokes a CPI signed by the address.
+    fn invoke_signed<'info>(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo<'info>],
+        bump: u8,
+    ) -> ProgramResult {
+        invoke_signed_with_bump(instruction, account_infos, &self.seeds(), bump)
+    }
+}
+
+/// A value that can be used as the seed of a `Pda`.
+///
+/// Integers are encoded little-endian, so clients should derive with `to_le_bytes()`.
+pub trait PdaSeed {
+    fn as_seed(&self) -> &[u8];
+}
+
+impl PdaSeed for Pubkey {
+    fn as_seed(&self) -> &[u8] {
+        self.as_ref()
+    }
+}
+
+impl PdaSeed for u8 {
+    fn as_seed(&self) -> &[u8] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl<const N: usize> PdaSeed for [u8; N] {
+    fn as_seed(&self) -> &[u8] {
+        self
+    }
+}
+
+macro_rules! impl_pda_seed {
+    ($($ty:ty),*) => {
+        $(
+            // Solana targets are little-endian, so the bytes in memory match `to_le_bytes`.
+            #[cfg(target_endian = "little")]
+            impl PdaSeed for $ty {
+                fn as_seed(&self) -> &[u8] {
+                    bytemuck::bytes_of(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_pda_seed!(u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// The seeds of a `Pda`, with room for a bump.
+///
+/// Dereferences to `&[&[u8]]`, so it can be passed to any function that takes seeds.
+#[derive(Clone, Copy, Debug)]
+pub struct PdaSeeds<'a> {
+    seeds: [&'a [u8]; MAX_SEEDS + 1],
+    len: usize,
+}
+
+impl<'a> PdaSeeds<'a> {
+    pub fn new() -> Self {
+        Self {
+            seeds: [&[]; MAX_SEEDS + 1],
+            len: 0,
+        }
+    }
+
+    /// Appends a seed.
+    ///
+    /// Panics if there are already `MAX_SEEDS` seeds and a bump.
+    pub fn with(mut self, seed: &'a [u8]) -> Self {
+        assert!(self.len <= MAX_SEEDS, "too many PDA seeds");
+        self.seeds[self.len] = seed;
+        self.len += 1;
+        self
+    }
+
+    pub fn as_slice(&self) -> &[&'a [u8]] {
+        &self.seeds[..self.len]
+    }
+}
+
+impl Default for PdaSeeds<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> std::ops::Deref for PdaSeeds<'a> {
+    type Target = [&'a [u8]];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    struct Counter {
+        value: u64,
+    }
+
+    impl Discriminator for Counter {
+        fn discriminator() -> u8 {
+            0
+        }
+    }
+
+    struct CounterPda {
+        authority: Pubkey,
+        id: u64,
+    }
+
+    impl Pda for CounterPda {
+        const PROGRAM_ID: Pubkey = PROGRAM_ID;
+
+        fn seeds(&self) -> PdaSeeds<'_> {
+            PdaSeeds::new()
+                .with(b"counter")
+                .with(self.authority.as_seed())
+                .with(self.id.as_seed())
+        }
+    }
+
+    #[test]
+    fn test_pda() {
+        let authority = Pubkey::new_unique();
+        let pda = CounterPda { authority, id: 42 };
+        let (address, bump) = pda.find();
+        assert_eq!(
+            (address, bump),
+            Pubkey::find_program_address(
+                &[b"counter", authority.as_ref(), &42u64.to_le_bytes()],
+                &PROGRAM_ID
+            )
+        );
+        assert_eq!(4, pda.signer_seeds(&[bump]).len());
+        assert_eq!(&[bump], pda.signer_seeds(&[bump])[3]);
+
+        let mut lamports = 0;
+        let mut data = [];
+        let info = AccountInfo::new(
+            &address,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &PROGRAM_ID,
+            false,
+            0,
+        );
+        assert_eq!(Ok(bump), pda.validate(&info));
+        assert!(pda.validate_with_bump(&info, bump).is_ok());
+        assert!(CounterPda { authority, id: 43 }.validate(&info).is_err());
+
+        // Accounts are only created with a bump that derives their address.
+        assert_eq!(
+            pda.create_account::<Counter>(&info, &info, &info, bump.wrapping_sub(1)),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "too many PDA seeds")]
+    fn test_pda_seeds_limit() {
+        (0..=MAX_SEEDS + 1).fold(PdaSeeds::new(), |seeds, _| seeds.with(b"seed"));
+    }
+}
@@ -3,7 +3,7 @@ use solana_program::{account_info::AccountInfo, program_error::ProgramError, pub
 
 use crate::trace;
 
-use super::{AccountDeserialize, Discriminator};
+use super::{account_size_with_tail, AccountDeserialize, AccountHeaderDeserialize, Discriminator};
 
 pub trait AccountInfoValidation {
     fn is_signer(&self) -> Result<&Self, ProgramError>;
@@ -16,6 +16,25 @@ pub trait AccountInfoValidation {
     fn has_address(&self, address: &Pubkey) -> Result<&Self, ProgramError>;
     fn has_owner(&self, program_id: &Pubkey) -> Result<&Self, ProgramError>;
     fn has_seeds(&self, seeds: &[&[u8]], program_id: &Pubkey) -> Result<&Self, ProgramError>;
+
+    /// Checks the account is a PDA of the seeds and returns its canonical bump, so later CPIs and
+    /// validations can skip the bump search.
+    fn has_seeds_returning_bump(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<u8, ProgramError>;
+
+    /// Checks the account is a PDA of the seeds and a known bump, without searching for the bump.
+    ///
+    /// The bump is not checked to be canonical, so it should come from a trusted source such as
+    /// the account header or an earlier `has_seeds_returning_bump`.
+    fn has_seeds_with_bump(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+        bump: u8,
+    ) -> Result<&Self, ProgramError>;
 }
 
 impl AccountInfoValidation for AccountInfo<'_> {
@@ -66,9 +85,12 @@ impl AccountInfoValidation for AccountInfo<'_> {
     #[track_caller]
     fn is_type<T: Discriminator>(&self, program_id: &Pubkey) -> Result<&Self, ProgramError> {
         self.has_owner(program_id)?;
-        if self.try_borrow_data()?[0].ne(&T::discriminator()) {
+        if !self
+            .try_borrow_data()?
+            .starts_with(T::discriminator_bytes())
+        {
             return Err(trace(
-                format!("Account is not of type {}", T::discriminator()).as_str(),
+                format!("Account is not of type {:?}", T::discriminator_bytes()).as_str(),
                 ProgramError::InvalidAccountData,
             ));
         }
@@ -110,6 +132,16 @@ impl AccountInfoValidation for AccountInfo<'_> {
 
     #[track_caller]
     fn has_seeds(&self, seeds: &[&[u8]], program_id: &Pubkey) -> Result<&Self, ProgramError> {
+        self.has_seeds_returning_bump(seeds, program_id)?;
+        Ok(self)
+    }
+
+    #[track_caller]
+    fn has_seeds_returning_bump(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<u8, ProgramError> {
         let pda = Pubkey::find_program_address(seeds, program_id);
         if self.key.ne(&pda.0) {
             return Err(trace(
@@ -117,6 +149,30 @@ impl AccountInfoValidation for AccountInfo<'_> {
                 ProgramError::InvalidSeeds,
             ));
         }
+        Ok(pda.1)
+    }
+
+    #[track_caller]
+    fn has_seeds_with_bump(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+        bump: u8,
+    ) -> Result<&Self, ProgramError> {
+        // Combine seeds
+        let bump: &[u8] = &[bump];
+        let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
+        combined_seeds.extend_from_slice(seeds);
+        combined_seeds.push(bump);
+
+        let pda = Pubkey::create_program_address(&combined_seeds, program_id)
+            .map_err(|err| trace("Account has invalid seeds", err.into()))?;
+        if self.key.ne(&pda) {
+            return Err(trace(
+                format!("Account has invalid seeds {} != {}", self.key, pda).as_str(),
+                ProgramError::InvalidSeeds,
+            ));
+        }
         Ok(self)
     }
 }
@@ -133,6 +189,20 @@ pub trait AsAccount {
     fn as_account_mut<T>(&self, program_id: &Pubkey) -> Result<&mut T, ProgramError>
     where
         T: AccountDeserialize + Discriminator + Pod;
+
+    fn as_account_with_tail<H, E>(&self, program_id: &Pubkey) -> Result<(&H, &[E]), ProgramError>
+    where
+        H: AccountHeaderDeserialize + Discriminator + Pod,
+        E: Pod;
+
+    #[allow(clippy::mut_from_ref)]
+    fn as_account_with_tail_mut<H, E>(
+        &self,
+        program_id: &Pubkey,
+    ) -> Result<(&mut H, &mut [E]), ProgramError>
+    where
+        H: AccountHeaderDeserialize + Discriminator + Pod,
+        E: Pod;
 }
 
 impl AsAccount for AccountInfo<'_> {
@@ -196,34 +266,196 @@ impl AsAccount for AccountInfo<'_> {
             ))
         }
     }
+
+    #[track_caller]
+    fn as_account_with_tail<H, E>(&self, program_id: &Pubkey) -> Result<(&H, &[E]), ProgramError>
+    where
+        H: AccountHeaderDeserialize + Discriminator + Pod,
+        E: Pod,
+    {
+        unsafe {
+            // Validate account owner.
+            self.has_owner(program_id)?;
+
+            // Validate account data length.
+            let data = self.try_borrow_data()?;
+            validate_tail_len::<H, E>(data.len())?;
+
+            // Deserialize account data.
+            H::try_header_with_tail_from_bytes(std::slice::from_raw_parts(
+                data.as_ptr(),
+                data.len(),
+            ))
+        }
+    }
+
+    #[track_caller]
+    fn as_account_with_tail_mut<H, E>(
+        &self,
+        program_id: &Pubkey,
+    ) -> Result<(&mut H, &mut [E]), ProgramError>
+    where
+        H: AccountHeaderDeserialize + Discriminator + Pod,
+        E: Pod,
+    {
+        unsafe {
+            // Validate account owner.
+            self.has_owner(program_id)?;
+
+            // Validate account data length.
+            let mut data = self.try_borrow_mut_data()?;
+            validate_tail_len::<H, E>(data.len())?;
+
+            // Deserialize account data.
+            H::try_header_with_tail_from_bytes_mut(std::slice::from_raw_parts_mut(
+                data.as_mut_ptr(),
+                data.len(),
+            ))
+        }
+    }
+}
+
+/// Validates that account data holds a header of type `H` followed by a whole number of `E`.
+#[track_caller]
+fn validate_tail_len<H: Pod, E: Pod>(len: usize) -> Result<(), ProgramError> {
+    let min_len = account_size_with_tail::<H, E>(0);
+    let elem_size = std::mem::size_of::<E>();
+    if len < min_len
+        || (len - min_len)
+            .checked_rem(elem_size)
+            .is_some_and(|r| r != 0)
+    {
+        return Err(trace(
+            format!(
+                "Account data length is invalid {} != {} + n * {}",
+                len, min_len, elem_size
+            )
+            .as_str(),
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    Ok(())
 }
 
+/// Asserts conditions on parsed account data.
+///
+/// Every method has a default implementation, so account types only need an empty impl.
 pub trait AccountValidation {
+    #[track_caller]
     fn assert<F>(&self, condition: F) -> Result<&Self, ProgramError>
     where
-        F: Fn(&Self) -> bool;
+        F: Fn(&Self) -> bool,
+    {
+        if !condition(self) {
+            return Err(trace(
+                "Account data is invalid",
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        Ok(self)
+    }
 
+    #[track_caller]
     fn assert_err<F>(&self, condition: F, err: ProgramError) -> Result<&Self, ProgramError>
     where
-        F: Fn(&Self) -> bool;
+        F: Fn(&Self) -> bool,
+    {
+        if !condition(self) {
+            return Err(trace("Account data is invalid", err));
+        }
+        Ok(self)
+    }
 
+    #[track_caller]
     fn assert_msg<F>(&self, condition: F, msg: &str) -> Result<&Self, ProgramError>
     where
-        F: Fn(&Self) -> bool;
+        F: Fn(&Self) -> bool,
+    {
+        if !condition(self) {
+            return Err(trace(
+                format!("Account data is invalid: {}", msg).as_str(),
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        Ok(self)
+    }
 
+    #[track_caller]
     fn assert_mut<F>(&mut self, condition: F) -> Result<&mut Self, ProgramError>
     where
-        F: Fn(&Self) -> bool;
+        F: Fn(&Self) -> bool,
+    {
+        if !condition(self) {
+            return Err(trace(
+                "Account data is invalid",
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        Ok(self)
+    }
 
+    #[track_caller]
     fn assert_mut_err<F>(
         &mut self,
         condition: F,
         err: ProgramError,
     ) -> Result<&mut Self, ProgramError>
     where
-        F: Fn(&Self) -> bool;
+        F: Fn(&Self) -> bool,
+    {
+        if !condition(self) {
+            return Err(trace("Account data is invalid", err));
+        }
+        Ok(self)
+    }
 
+    #[track_caller]
     fn assert_mut_msg<F>(&mut self, condition: F, msg: &str) -> Result<&mut Self, ProgramError>
     where
-        F: Fn(&Self) -> bool;
+        F: Fn(&Self) -> bool,
+    {
+        if !condition(self) {
+            return Err(trace(
+                format!("Account data is invalid: {}", msg).as_str(),
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_seeds_with_bump() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"counter"];
+        let (pda, bump) = Pubkey::find_program_address(seeds, &program_id);
+        let mut lamports = 0;
+        let mut data = [];
+        let info = AccountInfo::new(
+            &pda,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        assert_eq!(Ok(bump), info.has_seeds_returning_bump(seeds, &program_id));
+        assert!(info.has_seeds_with_bump(seeds, &program_id, bump).is_ok());
+        assert!(info
+            .has_seeds_with_bump(seeds, &program_id, bump.wrapping_sub(1))
+            .is_err());
+        assert!(info
+            .has_seeds_with_bump(&[b"other"], &program_id, bump)
+            .is_err());
+        assert!(info
+            .has_seeds_returning_bump(&[b"other"], &program_id)
+            .is_err());
+    }
 }
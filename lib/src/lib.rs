@@ -1,20 +1,30 @@
 mod account;
 mod log;
 pub mod macros;
+#[cfg(feature = "metaplex")]
+mod metaplex;
 mod numeric;
+mod pod;
 #[cfg(feature = "spl")]
 mod spl;
+#[cfg(test)]
+mod test_utils;
 mod utils;
 
 pub use account::*;
 pub use log::*;
+#[cfg(feature = "metaplex")]
+pub use metaplex::*;
 pub use numeric::*;
+pub use pod::*;
 #[cfg(feature = "spl")]
 pub use spl::*;
 pub use utils::*;
 
+pub use bytemuck;
 pub use bytemuck::{Pod, Zeroable};
 pub use num_enum::{IntoPrimitive, TryFromPrimitive};
+pub use solana_program;
 pub use solana_program::{
     account_info::AccountInfo,
     clock::Clock,
@@ -26,4 +36,9 @@ pub use solana_program::{
     system_program, sysvar,
     sysvar::Sysvar,
 };
+#[cfg(feature = "spl")]
+pub use spl_token_2022::{extension::ExtensionType, state::AccountState};
+#[cfg(feature = "spl")]
+pub use spl_token_metadata_interface::state::Field as MetadataField;
+pub use steel_derive::{Account, Accounts, Event, Instruction, Pda};
 pub use thiserror::Error;
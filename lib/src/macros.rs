@@ -50,107 +50,7 @@ macro_rules! account {
             }
         }
 
-        impl $crate::AccountValidation for $struct_name {
-            #[track_caller]
-            fn assert<F>(
-                &self,
-                condition: F,
-            ) -> Result<&Self, solana_program::program_error::ProgramError>
-            where
-                F: Fn(&Self) -> bool,
-            {
-                if !condition(self) {
-                    return Err(trace(
-                        "Account data is invalid",
-                        solana_program::program_error::ProgramError::InvalidAccountData,
-                    ));
-                }
-                Ok(self)
-            }
-
-            #[track_caller]
-            fn assert_err<F>(
-                &self,
-                condition: F,
-                err: solana_program::program_error::ProgramError,
-            ) -> Result<&Self, solana_program::program_error::ProgramError>
-            where
-                F: Fn(&Self) -> bool,
-            {
-                if !condition(self) {
-                    return Err(trace("Account data is invalid", err));
-                }
-                Ok(self)
-            }
-
-            #[track_caller]
-            fn assert_msg<F>(
-                &self,
-                condition: F,
-                msg: &str,
-            ) -> Result<&Self, solana_program::program_error::ProgramError>
-            where
-                F: Fn(&Self) -> bool,
-            {
-                if !condition(self) {
-                    return Err(trace(
-                        format!("Account data is invalid: {}", msg).as_str(),
-                        solana_program::program_error::ProgramError::InvalidAccountData,
-                    ));
-                }
-                Ok(self)
-            }
-
-            #[track_caller]
-            fn assert_mut<F>(
-                &mut self,
-                condition: F,
-            ) -> Result<&mut Self, solana_program::program_error::ProgramError>
-            where
-                F: Fn(&Self) -> bool,
-            {
-                if !condition(self) {
-                    return Err(trace(
-                        "Account data is invalid",
-                        solana_program::program_error::ProgramError::InvalidAccountData,
-                    ));
-                }
-                Ok(self)
-            }
-
-            #[track_caller]
-            fn assert_mut_err<F>(
-                &mut self,
-                condition: F,
-                err: solana_program::program_error::ProgramError,
-            ) -> Result<&mut Self, solana_program::program_error::ProgramError>
-            where
-                F: Fn(&Self) -> bool,
-            {
-                if !condition(self) {
-                    return Err(trace("Account data is invalid", err));
-                }
-                Ok(self)
-            }
-
-            #[track_caller]
-            fn assert_mut_msg<F>(
-                &mut self,
-                condition: F,
-                msg: &str,
-            ) -> Result<&mut Self, solana_program::program_error::ProgramError>
-            where
-                F: Fn(&Self) -> bool,
-            {
-                if !condition(self) {
-                    return Err(trace(
-                        format!("Account data is invalid: {}", msg).as_str(),
-                        solana_program::program_error::ProgramError::InvalidAccountData,
-                    ));
-                }
-                Ok(self)
-            }
-        }
+        impl $crate::AccountValidation for $struct_name {}
     };
 }
 
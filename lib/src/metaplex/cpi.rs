@@ -0,0 +1,317 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::account::invoke_signed_with_bump;
+
+use super::{encoding::Writer, CollectionDetails, DataV2, METADATA_PROGRAM_ID};
+
+/// The instruction discriminator of `CreateMetadataAccountV3`.
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+/// The instruction discriminator of `UpdateMetadataAccountV2`.
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+/// The fields to change in `UpdateMetadataAccountV2`. Fields left as `None` are unchanged.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UpdateMetadataAccountArgsV2 {
+    pub data: Option<DataV2>,
+    pub update_authority: Option<Pubkey>,
+    pub primary_sale_happened: Option<bool>,
+    pub is_mutable: Option<bool>,
+}
+
+/// Builds a `CreateMetadataAccountV3` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v3_instruction(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    update_authority: &Pubkey,
+    update_authority_is_signer: bool,
+    data: &DataV2,
+    is_mutable: bool,
+    collection_details: Option<&CollectionDetails>,
+) -> Instruction {
+    let mut writer = Writer(vec![CREATE_METADATA_ACCOUNT_V3]);
+    data.write(&mut writer);
+    writer.bool(is_mutable);
+    writer.option(collection_details, |w, details| details.write(w));
+    Instruction {
+        program_id: METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*update_authority, update_authority_is_signer),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ],
+        data: writer.0,
+    }
+}
+
+/// Builds an `UpdateMetadataAccountV2` instruction.
+pub fn update_metadata_accounts_v2_instruction(
+    metadata: &Pubkey,
+    update_authority: &Pubkey,
+    args: &UpdateMetadataAccountArgsV2,
+) -> Instruction {
+    let mut writer = Writer(vec![UPDATE_METADATA_ACCOUNT_V2]);
+    writer.option(args.data.as_ref(), |w, data| data.write(w));
+    writer.option(args.update_authority.as_ref(), Writer::pubkey);
+    writer.option(args.primary_sale_happened.as_ref(), |w, value| {
+        w.bool(*value)
+    });
+    writer.option(args.is_mutable.as_ref(), |w, value| w.bool(*value));
+    Instruction {
+        program_id: METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data: writer.0,
+    }
+}
+
+/// Creates the metadata account of a mint, signed by its mint authority.
+///
+/// The update authority only needs to sign when it is the mint authority or a signer of the
+/// transaction.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v3<'info>(
+    mint_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    update_authority_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    data: &DataV2,
+    is_mutable: bool,
+    collection_details: Option<&CollectionDetails>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &create_metadata_accounts_v3_instruction(
+            metadata_info.key,
+            mint_info.key,
+            mint_authority_info.key,
+            payer_info.key,
+            update_authority_info.key,
+            update_authority_is_signer(update_authority_info, mint_authority_info),
+            data,
+            is_mutable,
+            collection_details,
+        ),
+        &[
+            metadata_program.clone(),
+            metadata_info.clone(),
+            mint_info.clone(),
+            mint_authority_info.clone(),
+            payer_info.clone(),
+            update_authority_info.clone(),
+            system_program.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v3_signed<'info>(
+    mint_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    update_authority_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    data: &DataV2,
+    is_mutable: bool,
+    collection_details: Option<&CollectionDetails>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, mint_authority_info.owner).1;
+    create_metadata_accounts_v3_signed_with_bump(
+        mint_authority_info,
+        metadata_info,
+        mint_info,
+        payer_info,
+        update_authority_info,
+        system_program,
+        metadata_program,
+        data,
+        is_mutable,
+        collection_details,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v3_signed_with_bump<'info>(
+    mint_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    update_authority_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    data: &DataV2,
+    is_mutable: bool,
+    collection_details: Option<&CollectionDetails>,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &create_metadata_accounts_v3_instruction(
+            metadata_info.key,
+            mint_info.key,
+            mint_authority_info.key,
+            payer_info.key,
+            update_authority_info.key,
+            update_authority_is_signer(update_authority_info, mint_authority_info),
+            data,
+            is_mutable,
+            collection_details,
+        ),
+        &[
+            metadata_program.clone(),
+            metadata_info.clone(),
+            mint_info.clone(),
+            mint_authority_info.clone(),
+            payer_info.clone(),
+            update_authority_info.clone(),
+            system_program.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Updates the metadata account of a mint, signed by its update authority.
+#[inline(always)]
+pub fn update_metadata_accounts_v2<'info>(
+    update_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    args: &UpdateMetadataAccountArgsV2,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &update_metadata_accounts_v2_instruction(
+            metadata_info.key,
+            update_authority_info.key,
+            args,
+        ),
+        &[
+            metadata_program.clone(),
+            metadata_info.clone(),
+            update_authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn update_metadata_accounts_v2_signed<'info>(
+    update_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    args: &UpdateMetadataAccountArgsV2,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, update_authority_info.owner).1;
+    update_metadata_accounts_v2_signed_with_bump(
+        update_authority_info,
+        metadata_info,
+        metadata_program,
+        args,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn update_metadata_accounts_v2_signed_with_bump<'info>(
+    update_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    args: &UpdateMetadataAccountArgsV2,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &update_metadata_accounts_v2_instruction(
+            metadata_info.key,
+            update_authority_info.key,
+            args,
+        ),
+        &[
+            metadata_program.clone(),
+            metadata_info.clone(),
+            update_authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+fn update_authority_is_signer(
+    update_authority_info: &AccountInfo,
+    mint_authority_info: &AccountInfo,
+) -> bool {
+    update_authority_info.is_signer || update_authority_info.key.eq(mint_authority_info.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_instruction_data() {
+        let data = DataV2 {
+            name: "Ore".to_string(),
+            symbol: "ORE".to_string(),
+            uri: "u".to_string(),
+            seller_fee_basis_points: 500,
+            ..Default::default()
+        };
+        let key = Pubkey::new_unique();
+        let ix = create_metadata_accounts_v3_instruction(
+            &key, &key, &key, &key, &key, true, &data, true, None,
+        );
+        assert_eq!(ix.program_id, METADATA_PROGRAM_ID);
+        assert_eq!(
+            ix.data,
+            [
+                &[33, 3, 0, 0, 0][..],
+                b"Ore",
+                &[3, 0, 0, 0],
+                b"ORE",
+                &[1, 0, 0, 0],
+                b"u",
+                &[244, 1, 0, 0, 0, 1, 0],
+            ]
+            .concat()
+        );
+
+        let new_authority = Pubkey::new_unique();
+        let ix = update_metadata_accounts_v2_instruction(
+            &key,
+            &key,
+            &UpdateMetadataAccountArgsV2 {
+                update_authority: Some(new_authority),
+                is_mutable: Some(false),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            ix.data,
+            [&[15, 0, 1][..], new_authority.as_ref(), &[0, 1, 0],].concat()
+        );
+    }
+}
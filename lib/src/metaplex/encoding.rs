@@ -0,0 +1,132 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::trace;
+
+/// Writes values in the borsh encoding used by the token metadata program.
+pub(crate) struct Writer(pub Vec<u8>);
+
+impl Writer {
+    pub fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.0.push(value as u8);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn pubkey(&mut self, value: &Pubkey) {
+        self.0.extend_from_slice(value.as_ref());
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.0
+            .extend_from_slice(&(value.len() as u32).to_le_bytes());
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    pub fn option<T>(&mut self, value: Option<&T>, write: impl FnOnce(&mut Self, &T)) {
+        match value {
+            Some(value) => {
+                self.u8(1);
+                write(self, value);
+            }
+            None => self.u8(0),
+        }
+    }
+}
+
+/// Reads values in the borsh encoding used by the token metadata program.
+pub(crate) struct Reader<'a>(pub &'a [u8]);
+
+impl Reader<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[track_caller]
+    pub fn bytes(&mut self, len: usize) -> Result<&[u8], ProgramError> {
+        if self.0.len() < len {
+            return Err(trace(
+                "Metadata account data is too short",
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    #[track_caller]
+    pub fn u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    #[track_caller]
+    pub fn bool(&mut self) -> Result<bool, ProgramError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(trace(
+                "Metadata account has an invalid bool",
+                ProgramError::InvalidAccountData,
+            )),
+        }
+    }
+
+    #[track_caller]
+    pub fn u16(&mut self) -> Result<u16, ProgramError> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    #[track_caller]
+    pub fn u32(&mut self) -> Result<u32, ProgramError> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    #[track_caller]
+    pub fn u64(&mut self) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    #[track_caller]
+    pub fn pubkey(&mut self) -> Result<Pubkey, ProgramError> {
+        Ok(Pubkey::new_from_array(self.bytes(32)?.try_into().unwrap()))
+    }
+
+    /// Reads a string, trimming the null padding the metadata program stores names with.
+    #[track_caller]
+    pub fn string(&mut self) -> Result<String, ProgramError> {
+        let len = self.u32()? as usize;
+        let bytes = self.bytes(len)?;
+        let value = std::str::from_utf8(bytes).map_err(|_| {
+            trace(
+                "Metadata account has an invalid string",
+                ProgramError::InvalidAccountData,
+            )
+        })?;
+        Ok(value.trim_end_matches('\0').to_string())
+    }
+
+    #[track_caller]
+    pub fn option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, ProgramError>,
+    ) -> Result<Option<T>, ProgramError> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => read(self).map(Some),
+            _ => Err(trace(
+                "Metadata account has an invalid option",
+                ProgramError::InvalidAccountData,
+            )),
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::trace;
+
+use super::encoding::{Reader, Writer};
+
+/// The account key of a metadata account.
+pub const METADATA_KEY: u8 = 4;
+
+/// The maximum length of a token name.
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// The maximum length of a token symbol.
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// The maximum length of a token uri.
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// The maximum number of creators of a token.
+pub const MAX_CREATOR_LIMIT: usize = 5;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    /// The creator's share of royalties, in percent.
+    pub share: u8,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CollectionDetails {
+    V1 { size: u64 },
+    V2 { padding: [u8; 8] },
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+    ProgrammableNonFungible,
+    ProgrammableNonFungibleEdition,
+}
+
+/// The data of a metadata account set on creation and update.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Royalties in basis points.
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+/// A parsed token metadata account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub collection_details: Option<CollectionDetails>,
+}
+
+impl Metadata {
+    /// Parses a metadata account.
+    ///
+    /// Fields added in later versions of the metadata program are optional, so accounts written
+    /// by older versions parse with them set to `None`.
+    #[track_caller]
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader(data);
+        if reader.u8()? != METADATA_KEY {
+            return Err(trace(
+                "Account is not a metadata account",
+                ProgramError::InvalidAccountData,
+            ));
+        }
+        let update_authority = reader.pubkey()?;
+        let mint = reader.pubkey()?;
+        let name = reader.string()?;
+        let symbol = reader.string()?;
+        let uri = reader.string()?;
+        let seller_fee_basis_points = reader.u16()?;
+        let creators = reader.option(read_creators)?;
+        let primary_sale_happened = reader.bool()?;
+        let is_mutable = reader.bool()?;
+
+        let mut metadata = Metadata {
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators,
+            primary_sale_happened,
+            is_mutable,
+            edition_nonce: None,
+            token_standard: None,
+            collection: None,
+            uses: None,
+            collection_details: None,
+        };
+        if reader.is_empty() {
+            return Ok(metadata);
+        }
+        metadata.edition_nonce = reader.option(Reader::u8)?;
+        if reader.is_empty() {
+            return Ok(metadata);
+        }
+        metadata.token_standard = reader.option(read_token_standard)?;
+        if reader.is_empty() {
+            return Ok(metadata);
+        }
+        metadata.collection = reader.option(read_collection)?;
+        if reader.is_empty() {
+            return Ok(metadata);
+        }
+        metadata.uses = reader.option(read_uses)?;
+        if reader.is_empty() {
+            return Ok(metadata);
+        }
+        metadata.collection_details = reader.option(read_collection_details)?;
+        Ok(metadata)
+    }
+}
+
+impl DataV2 {
+    pub(crate) fn write(&self, writer: &mut Writer) {
+        writer.string(&self.name);
+        writer.string(&self.symbol);
+        writer.string(&self.uri);
+        writer.u16(self.seller_fee_basis_points);
+        writer.option(self.creators.as_ref(), |w, creators| {
+            w.0.extend_from_slice(&(creators.len() as u32).to_le_bytes());
+            for creator in creators {
+                w.pubkey(&creator.address);
+                w.bool(creator.verified);
+                w.u8(creator.share);
+            }
+        });
+        writer.option(self.collection.as_ref(), |w, collection| {
+            w.bool(collection.verified);
+            w.pubkey(&collection.key);
+        });
+        writer.option(self.uses.as_ref(), |w, uses| {
+            w.u8(uses.use_method.into());
+            w.u64(uses.remaining);
+            w.u64(uses.total);
+        });
+    }
+}
+
+impl CollectionDetails {
+    pub(crate) fn write(&self, writer: &mut Writer) {
+        match self {
+            CollectionDetails::V1 { size } => {
+                writer.u8(0);
+                writer.u64(*size);
+            }
+            CollectionDetails::V2 { padding } => {
+                writer.u8(1);
+                writer.0.extend_from_slice(padding);
+            }
+        }
+    }
+}
+
+#[track_caller]
+fn read_creators(reader: &mut Reader) -> Result<Vec<Creator>, ProgramError> {
+    let len = reader.u32()? as usize;
+    if len > MAX_CREATOR_LIMIT {
+        return Err(trace(
+            "Metadata account has too many creators",
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    let mut creators = Vec::with_capacity(len);
+    for _ in 0..len {
+        creators.push(Creator {
+            address: reader.pubkey()?,
+            verified: reader.bool()?,
+            share: reader.u8()?,
+        });
+    }
+    Ok(creators)
+}
+
+#[track_caller]
+fn read_token_standard(reader: &mut Reader) -> Result<TokenStandard, ProgramError> {
+    TokenStandard::try_from(reader.u8()?).map_err(|_| {
+        trace(
+            "Metadata account has an invalid token standard",
+            ProgramError::InvalidAccountData,
+        )
+    })
+}
+
+#[track_caller]
+fn read_collection(reader: &mut Reader) -> Result<Collection, ProgramError> {
+    Ok(Collection {
+        verified: reader.bool()?,
+        key: reader.pubkey()?,
+    })
+}
+
+#[track_caller]
+fn read_uses(reader: &mut Reader) -> Result<Uses, ProgramError> {
+    let use_method = UseMethod::try_from(reader.u8()?).map_err(|_| {
+        trace(
+            "Metadata account has an invalid use method",
+            ProgramError::InvalidAccountData,
+        )
+    })?;
+    Ok(Uses {
+        use_method,
+        remaining: reader.u64()?,
+        total: reader.u64()?,
+    })
+}
+
+#[track_caller]
+fn read_collection_details(reader: &mut Reader) -> Result<CollectionDetails, ProgramError> {
+    match reader.u8()? {
+        0 => Ok(CollectionDetails::V1 {
+            size: reader.u64()?,
+        }),
+        1 => Ok(CollectionDetails::V2 {
+            padding: reader.bytes(8)?.try_into().unwrap(),
+        }),
+        _ => Err(trace(
+            "Metadata account has invalid collection details",
+            ProgramError::InvalidAccountData,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_bytes(data: &DataV2, trailing: &[u8]) -> Vec<u8> {
+        let mut writer = Writer(vec![METADATA_KEY]);
+        writer.pubkey(&Pubkey::new_from_array([1; 32]));
+        writer.pubkey(&Pubkey::new_from_array([2; 32]));
+        writer.string(&format!("{:\0<32}", data.name));
+        writer.string(&format!("{:\0<10}", data.symbol));
+        writer.string(&data.uri);
+        writer.u16(data.seller_fee_basis_points);
+        writer.u8(0);
+        writer.bool(false);
+        writer.bool(true);
+        writer.0.extend_from_slice(trailing);
+        writer.0
+    }
+
+    #[test]
+    fn test_metadata_parse() {
+        let data = DataV2 {
+            name: "Ore".to_string(),
+            symbol: "ORE".to_string(),
+            uri: "https://ore.supply/metadata.json".to_string(),
+            seller_fee_basis_points: 0,
+            ..Default::default()
+        };
+
+        // Accounts written by older versions end after `is_mutable`.
+        let metadata = Metadata::try_from_bytes(&metadata_bytes(&data, &[])).unwrap();
+        assert_eq!(metadata.mint, Pubkey::new_from_array([2; 32]));
+        assert_eq!(metadata.name, "Ore");
+        assert_eq!(metadata.symbol, "ORE");
+        assert_eq!(metadata.uri, data.uri);
+        assert!(metadata.is_mutable);
+        assert_eq!(metadata.token_standard, None);
+
+        // Current accounts are zero padded after the optional fields.
+        let mut trailing = vec![1, 255, 1, TokenStandard::Fungible.into()];
+        trailing.extend_from_slice(&[0; 64]);
+        let metadata = Metadata::try_from_bytes(&metadata_bytes(&data, &trailing)).unwrap();
+        assert_eq!(metadata.edition_nonce, Some(255));
+        assert_eq!(metadata.token_standard, Some(TokenStandard::Fungible));
+        assert_eq!(metadata.collection, None);
+        assert_eq!(metadata.collection_details, None);
+
+        let mut bytes = metadata_bytes(&data, &[]);
+        assert_eq!(
+            Metadata::try_from_bytes(&bytes[..40]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        bytes[0] = 6;
+        assert_eq!(
+            Metadata::try_from_bytes(&bytes),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
@@ -0,0 +1,16 @@
+mod cpi;
+mod encoding;
+mod metadata;
+mod pda;
+mod validation;
+
+pub use cpi::*;
+pub use metadata::*;
+pub use pda::*;
+pub use validation::*;
+
+use solana_program::pubkey::Pubkey;
+
+/// The Metaplex token metadata program.
+pub const METADATA_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
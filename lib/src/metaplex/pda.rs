@@ -0,0 +1,30 @@
+use solana_program::pubkey::Pubkey;
+
+use super::METADATA_PROGRAM_ID;
+
+/// The seed prefix of metadata program accounts.
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// The seed suffix of master edition accounts.
+pub const EDITION_SEED: &[u8] = b"edition";
+
+/// Returns the metadata account address of a mint.
+pub fn metadata_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[METADATA_SEED, METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METADATA_PROGRAM_ID,
+    )
+}
+
+/// Returns the master edition account address of a mint.
+pub fn master_edition_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            METADATA_SEED,
+            METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+            EDITION_SEED,
+        ],
+        &METADATA_PROGRAM_ID,
+    )
+}
@@ -0,0 +1,28 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{account::AccountInfoValidation, trace};
+
+use super::{metadata_pda, Metadata, METADATA_PROGRAM_ID};
+
+pub trait AsMetaplex {
+    fn as_metadata(&self) -> Result<Metadata, ProgramError>;
+    fn as_metadata_of(&self, mint: &Pubkey) -> Result<Metadata, ProgramError>;
+}
+
+impl AsMetaplex for AccountInfo<'_> {
+    #[track_caller]
+    fn as_metadata(&self) -> Result<Metadata, ProgramError> {
+        if self.owner.ne(&METADATA_PROGRAM_ID) {
+            return Err(trace(
+                "Metadata account has an invalid owner",
+                ProgramError::InvalidAccountOwner,
+            ));
+        }
+        Metadata::try_from_bytes(&self.try_borrow_data()?)
+    }
+
+    #[track_caller]
+    fn as_metadata_of(&self, mint: &Pubkey) -> Result<Metadata, ProgramError> {
+        self.has_address(&metadata_pda(mint).0)?.as_metadata()
+    }
+}
@@ -0,0 +1,160 @@
+use std::{fmt, str::FromStr};
+
+use fixed::types::I80F48;
+use solana_program::program_error::ProgramError;
+
+use crate::trace;
+
+use super::Numeric;
+
+impl Numeric {
+    /// Formats the `Numeric` as a UI amount, treating it as a raw token amount with the given
+    /// number of decimals.
+    ///
+    /// Fractions of a base unit are dropped and trailing zeros are trimmed, so `1_500_000` with
+    /// 6 decimals formats as `"1.5"`.
+    pub fn to_string_with_decimals(&self, decimals: u8) -> String {
+        let value = self.to_i80f48().round_to_zero();
+        let sign = if value < I80F48::ZERO { "-" } else { "" };
+        let amount = value.unsigned_abs().to_num::<u128>().to_string();
+
+        let decimals = decimals as usize;
+        let padded = format!("{:0>width$}", amount, width = decimals + 1);
+        let (int, frac) = padded.split_at(padded.len() - decimals);
+        let frac = frac.trim_end_matches('0');
+        if frac.is_empty() {
+            format!("{}{}", sign, int)
+        } else {
+            format!("{}{}.{}", sign, int, frac)
+        }
+    }
+}
+
+/// Formats the value as a decimal. A precision such as `{:.2}` rounds to that many decimal
+/// places.
+impl fmt::Display for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_i80f48(), f)
+    }
+}
+
+impl fmt::Debug for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Numeric").field(&self.to_i80f48()).finish()
+    }
+}
+
+/// Parses a decimal string such as `"-1.25"`.
+impl FromStr for Numeric {
+    type Err = ProgramError;
+
+    #[track_caller]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        I80F48::from_str(s)
+            .map(Numeric::from_i80f48)
+            .map_err(|err| {
+                trace(
+                    format!("Invalid numeric {:?}: {}", s, err).as_str(),
+                    ProgramError::InvalidArgument,
+                )
+            })
+    }
+}
+
+/// Serializes a [`Numeric`] as a decimal string instead of its raw bytes.
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Quote {
+///     #[serde(with = "steel::numeric_string")]
+///     price: Numeric,
+/// }
+/// ```
+pub mod numeric_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::Numeric;
+
+    pub fn serialize<S: Serializer>(value: &Numeric, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Numeric, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| D::Error::custom(format!("invalid numeric {:?}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[test]
+    fn test_numeric_display() {
+        let n = Numeric::from_fraction(5, 4);
+        assert_eq!(n.to_string(), "1.25");
+        assert_eq!(format!("{:.1}", n), "1.2");
+        assert_eq!(format!("{:.4}", n), "1.2500");
+        assert_eq!(Numeric::from_i64(-3).to_string(), "-3");
+        assert_eq!(format!("{:?}", n), "Numeric(1.25)");
+    }
+
+    #[test]
+    fn test_numeric_from_str() {
+        assert_eq!("1.25".parse::<Numeric>(), Ok(Numeric::from_fraction(5, 4)));
+        assert_eq!("-7".parse::<Numeric>(), Ok(Numeric::from_i64(-7)));
+        assert_eq!(
+            "1.2.3".parse::<Numeric>(),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        let n = Numeric::from_fraction(1, 3);
+        assert_eq!(n.to_string().parse::<Numeric>(), Ok(n));
+    }
+
+    #[test]
+    fn test_numeric_to_string_with_decimals() {
+        assert_eq!(
+            Numeric::from_u64(1_500_000).to_string_with_decimals(6),
+            "1.5"
+        );
+        assert_eq!(
+            Numeric::from_u64(42).to_string_with_decimals(9),
+            "0.000000042"
+        );
+        assert_eq!(Numeric::from_u64(7_000).to_string_with_decimals(3), "7");
+        assert_eq!(Numeric::from_u64(123).to_string_with_decimals(0), "123");
+        assert_eq!(Numeric::from_i64(-2_500).to_string_with_decimals(3), "-2.5");
+        assert_eq!(
+            Numeric::from_fraction(1, 2)
+                .checked_sub(Numeric::ONE)
+                .unwrap()
+                .to_string_with_decimals(2),
+            "0"
+        );
+        assert_eq!(
+            Numeric::from_fraction(2_001, 2).to_string_with_decimals(3),
+            "1"
+        );
+    }
+
+    #[test]
+    fn test_numeric_string_serde() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Quote {
+            #[serde(with = "numeric_string")]
+            price: Numeric,
+        }
+
+        let quote = Quote {
+            price: Numeric::from_fraction(3, 8),
+        };
+        let json = serde_json::to_string(&quote).unwrap();
+        assert_eq!(json, r#"{"price":"0.375"}"#);
+        assert_eq!(serde_json::from_str::<Quote>(&json).unwrap(), quote);
+        assert!(serde_json::from_str::<Quote>(r#"{"price":"abc"}"#).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use fixed::types::I80F48;
+use solana_program::program_error::ProgramError;
+
+use crate::trace;
+
+use super::{mul_div_u64, overflow, Numeric, RoundingMode};
+
+/// The number of basis points in one.
+pub const BPS_PER_UNIT: u64 = 10_000;
+
+/// The number of percentage points in one.
+pub const PERCENT_PER_UNIT: u64 = 100;
+
+/// The natural logarithm of 2.
+const LN_2: I80F48 = I80F48::lit("0.693147180559945309417232121458");
+
+/// An upper bound on series terms, well past the point where terms vanish at 48 fractional bits.
+const MAX_SERIES_TERMS: u32 = 64;
+
+impl Numeric {
+    /// Creates a new `Numeric` from a number of basis points, where 10,000 basis points is one.
+    pub fn from_bps(bps: u64) -> Self {
+        Self::from_fraction(bps, BPS_PER_UNIT)
+    }
+
+    /// Returns the `Numeric` as a number of basis points using the given rounding mode.
+    #[track_caller]
+    pub fn to_bps(&self, mode: RoundingMode) -> Result<u64, ProgramError> {
+        self.mul_rounded(Numeric::from_u64(BPS_PER_UNIT), mode)?
+            .to_u64_rounded(mode)
+    }
+
+    /// Creates a new `Numeric` from a percentage, where 100 percent is one.
+    pub fn from_percent(percent: u64) -> Self {
+        Self::from_fraction(percent, PERCENT_PER_UNIT)
+    }
+
+    /// Raises the `Numeric` to an integer power by repeated squaring.
+    ///
+    /// Returns an error on overflow, or if the value is zero and the exponent is negative.
+    #[track_caller]
+    pub fn powi(&self, exp: i32) -> Result<Self, ProgramError> {
+        let mut base = *self;
+        let mut n = exp.unsigned_abs();
+        let mut result = Numeric::ONE;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        if exp < 0 {
+            Numeric::ONE.checked_div(result)
+        } else {
+            Ok(result)
+        }
+    }
+
+    /// Raises the `Numeric` to a fractional power, approximated as `exp(exp * ln(self))`.
+    ///
+    /// Integer exponents are computed exactly with [`Numeric::powi`]. Returns an error if the
+    /// value is negative, or on overflow.
+    #[track_caller]
+    pub fn pow(&self, exp: Numeric) -> Result<Self, ProgramError> {
+        let e = exp.to_i80f48();
+        if e.frac() == I80F48::ZERO {
+            if let Some(n) = e.checked_to_num::<i32>() {
+                return self.powi(n);
+            }
+        }
+        let x = self.to_i80f48();
+        if x < I80F48::ZERO {
+            return Err(trace(
+                "Numeric pow of a negative base",
+                ProgramError::InvalidArgument,
+            ));
+        }
+        if x == I80F48::ZERO {
+            return if e > I80F48::ZERO {
+                Ok(Numeric::ZERO)
+            } else {
+                Numeric::ONE.checked_div(Numeric::ZERO)
+            };
+        }
+        self.ln()?.checked_mul(exp)?.exp()
+    }
+
+    /// Approximates `e` raised to the `Numeric`.
+    ///
+    /// The argument is reduced to `k * ln(2) + r` with `|r| <= ln(2) / 2` and `exp(r)` is summed
+    /// as a Taylor series, so the result is accurate to a few units in the last place.
+    #[track_caller]
+    pub fn exp(&self) -> Result<Self, ProgramError> {
+        let x = self.to_i80f48();
+        let k = (x / LN_2).round();
+        let r = x - k * LN_2;
+
+        let mut term = I80F48::ONE;
+        let mut sum = I80F48::ONE;
+        for i in 1..MAX_SERIES_TERMS {
+            term = term * r / I80F48::from_num(i);
+            if term == I80F48::ZERO {
+                break;
+            }
+            sum += term;
+        }
+
+        // Scale by 2^k. The largest power of two an I80F48 can hold is 2^78.
+        let k = k.to_num::<i32>();
+        if k > 78 {
+            Err(overflow("exp"))
+        } else if k >= 0 {
+            sum.checked_mul(I80F48::ONE << k)
+                .map(Self::from_i80f48)
+                .ok_or_else(|| overflow("exp"))
+        } else {
+            Ok(Self::from_i80f48(
+                sum.checked_shr(k.unsigned_abs()).unwrap_or(I80F48::ZERO),
+            ))
+        }
+    }
+
+    /// Approximates the natural logarithm of the `Numeric`.
+    ///
+    /// The argument is reduced to `2^k * m` with `m` in `[1, 2)` and `ln(m)` is summed as the
+    /// series `2 * atanh((m - 1) / (m + 1))`. Returns an error if the value is not positive.
+    #[track_caller]
+    pub fn ln(&self) -> Result<Self, ProgramError> {
+        let x = self.to_i80f48();
+        if x <= I80F48::ZERO {
+            return Err(trace(
+                "Numeric ln of a non-positive value",
+                ProgramError::InvalidArgument,
+            ));
+        }
+
+        // Normalize the mantissa into [1, 2).
+        let k = 127 - x.to_bits().leading_zeros() as i32 - 48;
+        let m = if k >= 0 { x >> k } else { x << -k };
+
+        let s = (m - I80F48::ONE) / (m + I80F48::ONE);
+        let s2 = s * s;
+        let mut term = s;
+        let mut sum = I80F48::ZERO;
+        for i in 0..MAX_SERIES_TERMS {
+            if term == I80F48::ZERO {
+                break;
+            }
+            sum += term / I80F48::from_num(2 * i + 1);
+            term *= s2;
+        }
+
+        Ok(Self::from_i80f48(sum * 2 + LN_2 * I80F48::from_num(k)))
+    }
+}
+
+/// Returns `bps` basis points of `amount` using the given rounding mode.
+#[track_caller]
+pub fn bps_of(amount: u64, bps: u64, mode: RoundingMode) -> Result<u64, ProgramError> {
+    mul_div_u64(amount, bps, BPS_PER_UNIT, mode)
+}
+
+/// Returns `percent` percent of `amount` using the given rounding mode.
+#[track_caller]
+pub fn percent_of(amount: u64, percent: u64, mode: RoundingMode) -> Result<u64, ProgramError> {
+    mul_div_u64(amount, percent, PERCENT_PER_UNIT, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn num(value: f64) -> Numeric {
+        Numeric::from_i80f48(I80F48::from_num(value))
+    }
+
+    fn assert_close(actual: Numeric, expected: f64) {
+        let actual = actual.to_i80f48().to_num::<f64>();
+        let tolerance = 1e-9 * expected.abs().max(1.0);
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn test_numeric_bps_percent() {
+        assert_eq!(bps_of(1_000_001, 30, RoundingMode::Down), Ok(3_000));
+        assert_eq!(bps_of(1_000_001, 30, RoundingMode::Up), Ok(3_001));
+        assert_eq!(percent_of(999, 50, RoundingMode::HalfEven), Ok(500));
+        assert_eq!(percent_of(997, 50, RoundingMode::HalfEven), Ok(498));
+        assert_eq!(
+            Numeric::from_bps(250).to_bps(RoundingMode::HalfEven),
+            Ok(250)
+        );
+        assert_eq!(Numeric::from_percent(50), num(0.5));
+    }
+
+    #[test]
+    fn test_numeric_powi() {
+        assert_eq!(num(1.5).powi(2), Ok(num(2.25)));
+        assert_eq!(num(2.0).powi(-2), Ok(num(0.25)));
+        assert_eq!(num(-3.0).powi(3), Ok(num(-27.0)));
+        assert_eq!(num(7.0).powi(0), Ok(Numeric::ONE));
+        assert_eq!(num(2.0).powi(80), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(Numeric::ZERO.powi(-1), Err(ProgramError::InvalidArgument));
+    }
+
+    #[test]
+    fn test_numeric_exp_ln() {
+        assert_close(Numeric::ZERO.exp().unwrap(), 1.0);
+        assert_close(Numeric::ONE.exp().unwrap(), std::f64::consts::E);
+        assert_close(num(-3.25).exp().unwrap(), (-3.25f64).exp());
+        assert_close(num(20.0).exp().unwrap(), 20f64.exp());
+        assert_eq!(num(60.0).exp(), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(num(-100.0).exp(), Ok(Numeric::ZERO));
+
+        assert_close(Numeric::ONE.ln().unwrap(), 0.0);
+        assert_close(num(10.0).ln().unwrap(), 10f64.ln());
+        assert_close(num(0.001).ln().unwrap(), 0.001f64.ln());
+        assert_eq!(Numeric::ZERO.ln(), Err(ProgramError::InvalidArgument));
+
+        assert_close(num(2.0).pow(num(0.5)).unwrap(), 2f64.sqrt());
+        assert_close(num(1.05).pow(num(12.5)).unwrap(), 1.05f64.powf(12.5));
+        assert_eq!(num(-2.0).pow(num(0.5)), Err(ProgramError::InvalidArgument));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_exp_ln_roundtrip(x in 0.001f64..1_000_000.0) {
+            let ln = num(x).ln().unwrap();
+            let actual = ln.exp().unwrap().to_i80f48().to_num::<f64>();
+            prop_assert!((actual - x).abs() <= 1e-9 * x.max(1.0), "{} != {}", actual, x);
+        }
+
+        #[test]
+        fn prop_exp_matches_f64(x in -30.0f64..30.0) {
+            let actual = num(x).exp().unwrap().to_i80f48().to_num::<f64>();
+            let expected = x.exp();
+            prop_assert!((actual - expected).abs() <= 1e-9 * expected.max(1.0));
+        }
+
+        #[test]
+        fn prop_bps_of_brackets(amount in any::<u64>(), bps in 0..=BPS_PER_UNIT) {
+            let down = bps_of(amount, bps, RoundingMode::Down).unwrap();
+            let up = bps_of(amount, bps, RoundingMode::Up).unwrap();
+            let exact = amount as u128 * bps as u128;
+            prop_assert!(down as u128 * 10_000 <= exact);
+            prop_assert!(up as u128 * 10_000 >= exact);
+            prop_assert!(up - down <= 1);
+        }
+    }
+}
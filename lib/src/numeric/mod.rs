@@ -0,0 +1,455 @@
+mod format;
+mod math;
+mod pod_fixed;
+mod rounding;
+
+pub use format::*;
+pub use math::*;
+pub use pod_fixed::*;
+pub use rounding::*;
+
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+use fixed::types::I80F48;
+use serde::{Deserialize, Serialize};
+use solana_program::program_error::ProgramError;
+
+use crate::{trace, Pod, Zeroable};
+
+/// A fixed-point number with 80 bits for the integer part and 48 bits for the fractional part.
+///
+/// The arithmetic operators panic on overflow and division by zero. Use the `checked_*` methods
+/// to return a `ProgramError` instead, or the `saturating_*` methods to clamp the result.
+///
+/// Serde serializes the raw bytes by default. Use [`numeric_string`] to serialize a decimal string.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, Serialize, Deserialize)]
+pub struct Numeric {
+    bits: [u8; 16],
+}
+
+impl Numeric {
+    /// The zero value.
+    pub const ZERO: Self = Numeric {
+        bits: I80F48::ZERO.to_bits().to_le_bytes(),
+    };
+
+    /// The one value.
+    pub const ONE: Self = Numeric {
+        bits: I80F48::ONE.to_bits().to_le_bytes(),
+    };
+
+    /// Creates a new `Numeric` from a `u64`.
+    pub fn from_u64(value: u64) -> Self {
+        let i = I80F48::from_num(value);
+        Self::from_i80f48(i)
+    }
+
+    /// Returns the `Numeric` as a `u64`.
+    pub fn to_u64(&self) -> u64 {
+        self.floor().to_i80f48().to_num::<u64>()
+    }
+
+    /// Creates a new `Numeric` from a `i64`.
+    pub fn from_i64(value: i64) -> Self {
+        let i = I80F48::from_num(value);
+        Self::from_i80f48(i)
+    }
+
+    /// Returns the `Numeric` as a `i64`.
+    pub fn to_i64(&self) -> i64 {
+        self.floor().to_i80f48().to_num::<i64>()
+    }
+
+    /// Creates a new `Numeric` from an `I80F48`.
+    pub fn from_i80f48(value: I80F48) -> Self {
+        Self {
+            bits: value.to_bits().to_le_bytes(),
+        }
+    }
+
+    /// Returns the `Numeric` as an `I80F48`.
+    pub fn to_i80f48(&self) -> I80F48 {
+        I80F48::from_bits(i128::from_le_bytes(self.bits))
+    }
+
+    /// Returns the `Numeric` as a `u64`, rounding down.
+    ///
+    /// Returns an error if the value is negative or too large to fit.
+    #[track_caller]
+    pub fn try_to_u64(&self) -> Result<u64, ProgramError> {
+        self.to_i80f48()
+            .floor()
+            .checked_to_num::<u64>()
+            .ok_or_else(|| out_of_range("u64"))
+    }
+
+    /// Returns the `Numeric` as an `i64`, rounding down.
+    ///
+    /// Returns an error if the value is too large or too small to fit.
+    #[track_caller]
+    pub fn try_to_i64(&self) -> Result<i64, ProgramError> {
+        self.to_i80f48()
+            .floor()
+            .checked_to_num::<i64>()
+            .ok_or_else(|| out_of_range("i64"))
+    }
+
+    /// Creates a new `Numeric` from a fraction.
+    pub fn from_fraction(numerator: u64, denominator: u64) -> Self {
+        let f = I80F48::from_num(numerator) / I80F48::from_num(denominator);
+        Self::from_i80f48(f)
+    }
+
+    /// Creates a new `Numeric` from a fraction, or returns an error if the denominator is zero.
+    #[track_caller]
+    pub fn try_from_fraction(numerator: u64, denominator: u64) -> Result<Self, ProgramError> {
+        Self::from_u64(numerator).checked_div(Self::from_u64(denominator))
+    }
+
+    /// Adds two `Numeric`s, or returns an error on overflow.
+    #[track_caller]
+    pub fn checked_add(&self, other: Numeric) -> Result<Self, ProgramError> {
+        self.to_i80f48()
+            .checked_add(other.to_i80f48())
+            .map(Self::from_i80f48)
+            .ok_or_else(|| overflow("add"))
+    }
+
+    /// Subtracts two `Numeric`s, or returns an error on overflow.
+    #[track_caller]
+    pub fn checked_sub(&self, other: Numeric) -> Result<Self, ProgramError> {
+        self.to_i80f48()
+            .checked_sub(other.to_i80f48())
+            .map(Self::from_i80f48)
+            .ok_or_else(|| overflow("sub"))
+    }
+
+    /// Multiplies two `Numeric`s, or returns an error on overflow.
+    #[track_caller]
+    pub fn checked_mul(&self, other: Numeric) -> Result<Self, ProgramError> {
+        self.to_i80f48()
+            .checked_mul(other.to_i80f48())
+            .map(Self::from_i80f48)
+            .ok_or_else(|| overflow("mul"))
+    }
+
+    /// Divides two `Numeric`s, or returns an error on division by zero or overflow.
+    #[track_caller]
+    pub fn checked_div(&self, other: Numeric) -> Result<Self, ProgramError> {
+        if other.to_i80f48() == I80F48::ZERO {
+            return Err(trace(
+                "Numeric division by zero",
+                ProgramError::InvalidArgument,
+            ));
+        }
+        self.to_i80f48()
+            .checked_div(other.to_i80f48())
+            .map(Self::from_i80f48)
+            .ok_or_else(|| overflow("div"))
+    }
+
+    /// Adds two `Numeric`s, saturating at the numeric bounds.
+    pub fn saturating_add(&self, other: Numeric) -> Self {
+        Self::from_i80f48(self.to_i80f48().saturating_add(other.to_i80f48()))
+    }
+
+    /// Subtracts two `Numeric`s, saturating at the numeric bounds.
+    pub fn saturating_sub(&self, other: Numeric) -> Self {
+        Self::from_i80f48(self.to_i80f48().saturating_sub(other.to_i80f48()))
+    }
+
+    /// Multiplies two `Numeric`s, saturating at the numeric bounds.
+    pub fn saturating_mul(&self, other: Numeric) -> Self {
+        Self::from_i80f48(self.to_i80f48().saturating_mul(other.to_i80f48()))
+    }
+
+    /// Returns the ceiling of the `Numeric`.
+    pub fn ceil(&self) -> Self {
+        Self::from_i80f48(self.to_i80f48().ceil())
+    }
+
+    /// Returns the floor of the `Numeric`.
+    pub fn floor(&self) -> Self {
+        Self::from_i80f48(self.to_i80f48().floor())
+    }
+
+    /// Returns the absolute value of the `Numeric`.
+    pub fn abs(&self) -> Self {
+        Self::from_i80f48(self.to_i80f48().abs())
+    }
+
+    /// Returns the square root of the `Numeric`.
+    pub fn sqrt(&self) -> Self {
+        Self::from_i80f48(self.to_i80f48().sqrt())
+    }
+
+    /// Returns true if the `Numeric` is negative.
+    pub fn is_negative(&self) -> bool {
+        self.to_i80f48().is_negative()
+    }
+
+    /// Returns true if the `Numeric` is positive.
+    pub fn is_positive(&self) -> bool {
+        self.to_i80f48().is_positive()
+    }
+}
+
+impl Add for Numeric {
+    type Output = Numeric;
+
+    fn add(self, other: Numeric) -> Numeric {
+        Numeric::from_i80f48(self.to_i80f48() + other.to_i80f48())
+    }
+}
+
+impl Sub for Numeric {
+    type Output = Numeric;
+
+    fn sub(self, other: Numeric) -> Numeric {
+        Numeric::from_i80f48(self.to_i80f48() - other.to_i80f48())
+    }
+}
+
+impl Mul for Numeric {
+    type Output = Numeric;
+
+    fn mul(self, other: Numeric) -> Numeric {
+        Numeric::from_i80f48(self.to_i80f48() * other.to_i80f48())
+    }
+}
+
+impl Div for Numeric {
+    type Output = Numeric;
+
+    fn div(self, other: Numeric) -> Numeric {
+        Numeric::from_i80f48(self.to_i80f48() / other.to_i80f48())
+    }
+}
+
+impl AddAssign for Numeric {
+    fn add_assign(&mut self, other: Numeric) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Numeric {
+    fn sub_assign(&mut self, other: Numeric) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Numeric {
+    fn mul_assign(&mut self, other: Numeric) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Numeric {
+    fn div_assign(&mut self, other: Numeric) {
+        *self = *self / other;
+    }
+}
+
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_i80f48() == other.to_i80f48()
+    }
+}
+
+impl Eq for Numeric {}
+
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Numeric {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_i80f48().cmp(&other.to_i80f48())
+    }
+}
+
+impl Hash for Numeric {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state)
+    }
+}
+
+#[track_caller]
+fn overflow(op: &str) -> ProgramError {
+    trace(
+        format!("Numeric overflow in {}", op).as_str(),
+        ProgramError::ArithmeticOverflow,
+    )
+}
+
+#[track_caller]
+fn out_of_range(ty: &str) -> ProgramError {
+    trace(
+        format!("Numeric is out of range for {}", ty).as_str(),
+        ProgramError::ArithmeticOverflow,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_add() {
+        let a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        let result = a + b;
+        assert_eq!(result.to_i80f48(), fixed::types::I80F48::from_num(8));
+    }
+
+    #[test]
+    fn test_numeric_sub() {
+        let a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        let result = a - b;
+        assert_eq!(result.to_i80f48(), fixed::types::I80F48::from_num(2));
+    }
+
+    #[test]
+    fn test_numeric_mul() {
+        let a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        let result = a * b;
+        assert_eq!(result.to_i80f48(), fixed::types::I80F48::from_num(15));
+    }
+
+    #[test]
+    fn test_numeric_div() {
+        let a = Numeric::from_i80f48(fixed::types::I80F48::from_num(15));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        let result = a / b;
+        assert_eq!(result.to_i80f48(), fixed::types::I80F48::from_num(5));
+    }
+
+    #[test]
+    fn test_numeric_add_assign() {
+        let mut a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        a += b;
+        assert_eq!(a.to_i80f48(), fixed::types::I80F48::from_num(8));
+    }
+
+    #[test]
+    fn test_numeric_sub_assign() {
+        let mut a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        a -= b;
+        assert_eq!(a.to_i80f48(), fixed::types::I80F48::from_num(2));
+    }
+
+    #[test]
+    fn test_numeric_mul_assign() {
+        let mut a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        a *= b;
+        assert_eq!(a.to_i80f48(), fixed::types::I80F48::from_num(15));
+    }
+
+    #[test]
+    fn test_numeric_div_assign() {
+        let mut a = Numeric::from_i80f48(fixed::types::I80F48::from_num(15));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        a /= b;
+        assert_eq!(a.to_i80f48(), fixed::types::I80F48::from_num(5));
+    }
+
+    #[test]
+    fn test_numeric_checked() {
+        let max = Numeric::from_i80f48(I80F48::MAX);
+        let min = Numeric::from_i80f48(I80F48::MIN);
+        let two = Numeric::from_u64(2);
+
+        assert_eq!(two.checked_add(two), Ok(Numeric::from_u64(4)));
+        assert_eq!(
+            max.checked_add(Numeric::ONE),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            min.checked_sub(Numeric::ONE),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(max.checked_mul(two), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(
+            two.checked_div(Numeric::ZERO),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            min.checked_div(Numeric::from_i64(-1)),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+
+        assert_eq!(max.saturating_add(Numeric::ONE), max);
+        assert_eq!(min.saturating_sub(Numeric::ONE), min);
+        assert_eq!(min.saturating_mul(two), min);
+    }
+
+    #[test]
+    fn test_numeric_try_conversions() {
+        assert_eq!(
+            Numeric::try_from_fraction(1, 0),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            Numeric::try_from_fraction(3, 2).unwrap().to_i80f48(),
+            I80F48::from_num(1.5)
+        );
+
+        assert_eq!(Numeric::from_fraction(7, 2).try_to_u64(), Ok(3));
+        assert_eq!(
+            Numeric::from_i64(-1).try_to_u64(),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            Numeric::from_u64(u64::MAX).try_to_i64(),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            Numeric::from_i80f48(I80F48::MAX).try_to_u64(),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(Numeric::from_i64(-5).try_to_i64(), Ok(-5));
+    }
+
+    #[test]
+    fn test_numeric_comparison() {
+        let a = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+        let b = Numeric::from_i80f48(fixed::types::I80F48::from_num(3));
+        let c = Numeric::from_i80f48(fixed::types::I80F48::from_num(5));
+
+        assert!(a > b);
+        assert!(b < a);
+        assert!(a >= c);
+        assert!(c <= a);
+        assert_eq!(a, c);
+        assert!(a != b);
+    }
+
+    #[test]
+    fn test_numeric_ord() {
+        let a = Numeric::from_fraction(3, 2);
+        let b = Numeric::from_i64(-2);
+        assert_eq!(a.min(b), b);
+        assert_eq!(a.max(b), a);
+        assert_eq!(Numeric::from_u64(5).clamp(b, a), a);
+        assert_eq!(Numeric::from_i64(-5).clamp(b, a), b);
+
+        let mut values = vec![a, b, Numeric::ZERO];
+        values.sort();
+        assert_eq!(values, vec![b, Numeric::ZERO, a]);
+
+        let set = std::collections::HashSet::from([a, b, Numeric::from_fraction(3, 2)]);
+        assert_eq!(set.len(), 2);
+    }
+}
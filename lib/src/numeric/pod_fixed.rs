@@ -0,0 +1,486 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::Hash,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+use bytemuck::{Pod, Zeroable};
+use fixed::{
+    traits::{Fixed, FromFixed, ToFixed},
+    types::{
+        extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8},
+        I32F32, I64F64, I80F48, U32F32, U64F64, U80F48,
+    },
+    FixedI128, FixedI16, FixedI32, FixedI64, FixedI8, FixedU128, FixedU16, FixedU32, FixedU64,
+    FixedU8,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use solana_program::program_error::ProgramError;
+
+use crate::{trace, PodUnaligned};
+
+use super::{overflow, Numeric};
+
+/// A `fixed` crate type that can be stored in a [`PodFixed`].
+pub trait PodFixedType: Fixed + 'static {
+    /// The little-endian byte representation of the type.
+    type Repr: Pod + Eq + Hash + fmt::Debug + Serialize + DeserializeOwned;
+
+    /// Converts the value to its byte representation.
+    fn to_repr(self) -> Self::Repr;
+
+    /// Converts a byte representation to a value.
+    fn from_repr(repr: Self::Repr) -> Self;
+}
+
+macro_rules! pod_fixed_type {
+    ($($fixed:ident: $le_eq:ident, $size:literal),* $(,)?) => {
+        $(
+            impl<Frac: $le_eq> PodFixedType for $fixed<Frac> {
+                type Repr = [u8; $size];
+
+                fn to_repr(self) -> Self::Repr {
+                    self.to_le_bytes()
+                }
+
+                fn from_repr(repr: Self::Repr) -> Self {
+                    Self::from_le_bytes(repr)
+                }
+            }
+        )*
+    };
+}
+
+pod_fixed_type!(
+    FixedI8: LeEqU8, 1,
+    FixedI16: LeEqU16, 2,
+    FixedI32: LeEqU32, 4,
+    FixedI64: LeEqU64, 8,
+    FixedI128: LeEqU128, 16,
+    FixedU8: LeEqU8, 1,
+    FixedU16: LeEqU16, 2,
+    FixedU32: LeEqU32, 4,
+    FixedU64: LeEqU64, 8,
+    FixedU128: LeEqU128, 16,
+);
+
+/// A fixed-point number of any `fixed` crate precision, stored as little-endian bytes.
+///
+/// Unlike the `fixed` types themselves, a `PodFixed` has an alignment of 1, so it can be placed
+/// anywhere in an account without introducing padding. Like [`Numeric`], the arithmetic operators
+/// panic on overflow and division by zero, while the `checked_*` methods return a `ProgramError`.
+#[repr(transparent)]
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PodFixed<F: PodFixedType> {
+    bits: F::Repr,
+}
+
+/// An unsigned fixed-point number with 32 integer and 32 fractional bits.
+pub type PodU32F32 = PodFixed<U32F32>;
+
+/// A signed fixed-point number with 32 integer and 32 fractional bits.
+pub type PodI32F32 = PodFixed<I32F32>;
+
+/// An unsigned fixed-point number with 64 integer and 64 fractional bits.
+pub type PodU64F64 = PodFixed<U64F64>;
+
+/// A signed fixed-point number with 64 integer and 64 fractional bits.
+pub type PodI64F64 = PodFixed<I64F64>;
+
+/// An unsigned fixed-point number with 80 integer and 48 fractional bits.
+pub type PodU80F48 = PodFixed<U80F48>;
+
+/// A signed fixed-point number with 80 integer and 48 fractional bits, with the same layout as
+/// [`Numeric`].
+pub type PodI80F48 = PodFixed<I80F48>;
+
+unsafe impl<F: PodFixedType> Zeroable for PodFixed<F> {}
+
+unsafe impl<F: PodFixedType> Pod for PodFixed<F> {}
+
+unsafe impl<F: PodFixedType> PodUnaligned for PodFixed<F> where F::Repr: PodUnaligned {}
+
+impl<F: PodFixedType> PodFixed<F> {
+    /// Creates a new value from a `fixed` number.
+    pub fn new(value: F) -> Self {
+        Self {
+            bits: value.to_repr(),
+        }
+    }
+
+    /// Returns the value as a `fixed` number.
+    pub fn get(&self) -> F {
+        F::from_repr(self.bits)
+    }
+
+    /// Sets the value.
+    pub fn set(&mut self, value: F) {
+        self.bits = value.to_repr();
+    }
+
+    /// The zero value.
+    pub fn zero() -> Self {
+        Self::new(F::ZERO)
+    }
+
+    /// The smallest value.
+    pub fn min_value() -> Self {
+        Self::new(F::MIN)
+    }
+
+    /// The largest value.
+    pub fn max_value() -> Self {
+        Self::new(F::MAX)
+    }
+
+    /// Creates a new value from any number, or returns an error if it is out of range.
+    #[track_caller]
+    pub fn from_num<N: ToFixed>(value: N) -> Result<Self, ProgramError> {
+        F::checked_from_num(value)
+            .map(Self::new)
+            .ok_or_else(|| overflow("from_num"))
+    }
+
+    /// Returns the value as any number, or returns an error if it is out of range.
+    ///
+    /// Integer targets are rounded down.
+    #[track_caller]
+    pub fn to_num<N: FromFixed>(&self) -> Result<N, ProgramError> {
+        self.get()
+            .floor()
+            .checked_to_num()
+            .ok_or_else(|| overflow("to_num"))
+    }
+
+    /// Creates a new value from a `u64`, or returns an error if it is out of range.
+    #[track_caller]
+    pub fn from_u64(value: u64) -> Result<Self, ProgramError> {
+        Self::from_num(value)
+    }
+
+    /// Returns the value as a `u64`, rounding down.
+    #[track_caller]
+    pub fn to_u64(&self) -> Result<u64, ProgramError> {
+        self.to_num()
+    }
+
+    /// Creates a new value from an `i64`, or returns an error if it is out of range.
+    #[track_caller]
+    pub fn from_i64(value: i64) -> Result<Self, ProgramError> {
+        Self::from_num(value)
+    }
+
+    /// Returns the value as an `i64`, rounding down.
+    #[track_caller]
+    pub fn to_i64(&self) -> Result<i64, ProgramError> {
+        self.to_num()
+    }
+
+    /// Creates a new value from a fraction, or returns an error if the denominator is zero or
+    /// the result is out of range.
+    ///
+    /// The fraction is computed at `Numeric` precision and truncated to the target precision.
+    #[track_caller]
+    pub fn from_fraction(numerator: u64, denominator: u64) -> Result<Self, ProgramError> {
+        PodI80F48::from(Numeric::try_from_fraction(numerator, denominator)?)
+            .try_convert_truncating()
+    }
+
+    /// Converts the value to another precision, or returns an error if the value cannot be
+    /// represented exactly.
+    #[track_caller]
+    pub fn try_convert<G: PodFixedType>(&self) -> Result<PodFixed<G>, ProgramError> {
+        let value = self.get();
+        G::checked_from_fixed(value)
+            .filter(|converted| F::checked_from_fixed(*converted) == Some(value))
+            .map(PodFixed::new)
+            .ok_or_else(|| {
+                trace(
+                    format!(
+                        "Fixed-point value {} is not representable as {}",
+                        value,
+                        std::any::type_name::<G>()
+                    )
+                    .as_str(),
+                    ProgramError::InvalidArgument,
+                )
+            })
+    }
+
+    /// Converts the value to another precision, dropping any fractional bits the target cannot
+    /// hold. Returns an error if the integer part is out of range.
+    #[track_caller]
+    pub fn try_convert_truncating<G: PodFixedType>(&self) -> Result<PodFixed<G>, ProgramError> {
+        G::checked_from_fixed(self.get())
+            .map(PodFixed::new)
+            .ok_or_else(|| overflow("convert"))
+    }
+
+    /// Converts the value to a `Numeric`, or returns an error if it cannot be represented exactly.
+    #[track_caller]
+    pub fn try_to_numeric(&self) -> Result<Numeric, ProgramError> {
+        self.try_convert::<I80F48>().map(Numeric::from)
+    }
+
+    /// Adds two values, or returns an error on overflow.
+    #[track_caller]
+    pub fn checked_add(&self, other: Self) -> Result<Self, ProgramError> {
+        self.get()
+            .checked_add(other.get())
+            .map(Self::new)
+            .ok_or_else(|| overflow("add"))
+    }
+
+    /// Subtracts two values, or returns an error on overflow.
+    #[track_caller]
+    pub fn checked_sub(&self, other: Self) -> Result<Self, ProgramError> {
+        self.get()
+            .checked_sub(other.get())
+            .map(Self::new)
+            .ok_or_else(|| overflow("sub"))
+    }
+
+    /// Multiplies two values, or returns an error on overflow.
+    #[track_caller]
+    pub fn checked_mul(&self, other: Self) -> Result<Self, ProgramError> {
+        self.get()
+            .checked_mul(other.get())
+            .map(Self::new)
+            .ok_or_else(|| overflow("mul"))
+    }
+
+    /// Divides two values, or returns an error on division by zero or overflow.
+    #[track_caller]
+    pub fn checked_div(&self, other: Self) -> Result<Self, ProgramError> {
+        if other.get() == F::ZERO {
+            return Err(trace(
+                "Fixed-point division by zero",
+                ProgramError::InvalidArgument,
+            ));
+        }
+        self.get()
+            .checked_div(other.get())
+            .map(Self::new)
+            .ok_or_else(|| overflow("div"))
+    }
+
+    /// Adds two values, saturating at the numeric bounds.
+    pub fn saturating_add(&self, other: Self) -> Self {
+        Self::new(self.get().saturating_add(other.get()))
+    }
+
+    /// Subtracts two values, saturating at the numeric bounds.
+    pub fn saturating_sub(&self, other: Self) -> Self {
+        Self::new(self.get().saturating_sub(other.get()))
+    }
+
+    /// Multiplies two values, saturating at the numeric bounds.
+    pub fn saturating_mul(&self, other: Self) -> Self {
+        Self::new(self.get().saturating_mul(other.get()))
+    }
+
+    /// Returns the largest integer less than or equal to the value.
+    pub fn floor(&self) -> Self {
+        Self::new(self.get().floor())
+    }
+
+    /// Returns the smallest integer greater than or equal to the value, or returns an error on
+    /// overflow.
+    #[track_caller]
+    pub fn ceil(&self) -> Result<Self, ProgramError> {
+        self.get()
+            .checked_ceil()
+            .map(Self::new)
+            .ok_or_else(|| overflow("ceil"))
+    }
+}
+
+impl From<Numeric> for PodI80F48 {
+    fn from(value: Numeric) -> Self {
+        Self::new(value.to_i80f48())
+    }
+}
+
+impl From<PodI80F48> for Numeric {
+    fn from(value: PodI80F48) -> Self {
+        Numeric::from_i80f48(value.get())
+    }
+}
+
+impl Numeric {
+    /// Converts the `Numeric` to another fixed-point precision, or returns an error if it cannot
+    /// be represented exactly.
+    #[track_caller]
+    pub fn try_convert<G: PodFixedType>(&self) -> Result<PodFixed<G>, ProgramError> {
+        PodI80F48::from(*self).try_convert()
+    }
+}
+
+impl<F: PodFixedType> Clone for PodFixed<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F: PodFixedType> Copy for PodFixed<F> {}
+
+impl<F: PodFixedType> Default for PodFixed<F> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<F: PodFixedType> From<F> for PodFixed<F> {
+    fn from(value: F) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<F: PodFixedType> PartialEq for PodFixed<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<F: PodFixedType> Eq for PodFixed<F> {}
+
+impl<F: PodFixedType> PartialOrd for PodFixed<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: PodFixedType> Ord for PodFixed<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(&other.get())
+    }
+}
+
+impl<F: PodFixedType> Hash for PodFixed<F> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.bits.hash(state)
+    }
+}
+
+impl<F: PodFixedType> fmt::Debug for PodFixed<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+impl<F: PodFixedType> fmt::Display for PodFixed<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.get(), f)
+    }
+}
+
+macro_rules! pod_fixed_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident) => {
+        impl<F: PodFixedType> $trait for PodFixed<F> {
+            type Output = Self;
+
+            fn $fn(self, other: Self) -> Self {
+                Self::new(self.get().$fn(other.get()))
+            }
+        }
+
+        impl<F: PodFixedType> $assign_trait for PodFixed<F> {
+            fn $assign_fn(&mut self, other: Self) {
+                *self = (*self).$fn(other);
+            }
+        }
+    };
+}
+
+pod_fixed_op!(Add, add, AddAssign, add_assign);
+pod_fixed_op!(Sub, sub, SubAssign, sub_assign);
+pod_fixed_op!(Mul, mul, MulAssign, mul_assign);
+pod_fixed_op!(Div, div, DivAssign, div_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_fixed_layout() {
+        assert_eq!(std::mem::size_of::<PodU32F32>(), 8);
+        assert_eq!(std::mem::align_of::<PodU32F32>(), 1);
+        assert_eq!(std::mem::size_of::<PodU80F48>(), 16);
+        assert_eq!(std::mem::align_of::<PodI80F48>(), 1);
+
+        let value = PodI80F48::from(Numeric::from_fraction(3, 2));
+        assert_eq!(
+            bytemuck::bytes_of(&value),
+            bytemuck::bytes_of(&Numeric::from_fraction(3, 2))
+        );
+    }
+
+    #[test]
+    fn test_pod_fixed_arithmetic() {
+        let a = PodU32F32::from_num(5.5).unwrap();
+        let b = PodU32F32::from_u64(2).unwrap();
+        assert_eq!(a + b, PodU32F32::from_num(7.5).unwrap());
+        assert_eq!(a - b, PodU32F32::from_num(3.5).unwrap());
+        assert_eq!(a * b, PodU32F32::from_u64(11).unwrap());
+        assert_eq!(a / b, PodU32F32::from_num(2.75).unwrap());
+        assert!(a > b);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c.to_u64(), Ok(7));
+        assert_eq!(c.ceil().unwrap().to_u64(), Ok(8));
+
+        assert_eq!(b.checked_sub(a), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(
+            a.checked_div(PodU32F32::zero()),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(b.saturating_sub(a), PodU32F32::zero());
+        assert_eq!(
+            PodU32F32::max_value().saturating_add(b),
+            PodU32F32::max_value()
+        );
+        assert_eq!(
+            PodU32F32::from_u64(u64::MAX),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(PodI32F32::from_i64(-3).unwrap().to_i64(), Ok(-3));
+    }
+
+    #[test]
+    fn test_pod_fixed_conversions() {
+        let half = PodU32F32::from_fraction(1, 2).unwrap();
+        assert_eq!(half.try_to_numeric(), Ok(Numeric::from_fraction(1, 2)));
+        assert_eq!(half.try_convert::<U64F64>().unwrap().to_string(), "0.5");
+
+        // A third has more fractional bits than U32F32 can hold.
+        let third = Numeric::from_fraction(1, 3);
+        assert_eq!(
+            third.try_convert::<U32F32>(),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert!(third.try_convert::<U64F64>().is_ok());
+        assert!(PodI80F48::from(third)
+            .try_convert_truncating::<U32F32>()
+            .is_ok());
+
+        // Negative values do not fit unsigned precisions.
+        assert_eq!(
+            Numeric::from_i64(-1).try_convert::<U80F48>(),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            PodU80F48::max_value().try_convert_truncating::<U32F32>(),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_pod_fixed_serde() {
+        let value = PodI32F32::from_num(-1.25).unwrap();
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<PodI32F32>(&json).unwrap(), value);
+    }
+}
@@ -0,0 +1,345 @@
+use fixed::types::I80F48;
+use solana_program::program_error::ProgramError;
+
+use crate::trace;
+
+use super::{overflow, Numeric};
+
+/// The number of fractional bits in a `Numeric`.
+const FRAC_BITS: u32 = 48;
+
+/// The direction to round a result that cannot be represented exactly.
+///
+/// Protocols should round in their own favor, e.g. round fees charged `Up` and shares minted
+/// `Down`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round toward negative infinity.
+    Down,
+
+    /// Round toward positive infinity.
+    Up,
+
+    /// Round to the nearest value, breaking ties toward the even neighbor.
+    HalfEven,
+}
+
+impl Numeric {
+    /// Rounds the `Numeric` to an integer using the given rounding mode.
+    #[track_caller]
+    pub fn round(&self, mode: RoundingMode) -> Result<Self, ProgramError> {
+        let bits = self.to_i80f48().to_bits();
+        div_round(bits, 1, 1 << FRAC_BITS, mode)
+            .and_then(|i| i.checked_mul(1 << FRAC_BITS))
+            .map(|bits| Self::from_i80f48(I80F48::from_bits(bits)))
+            .ok_or_else(|| overflow("round"))
+    }
+
+    /// Returns the `Numeric` as a `u64` using the given rounding mode.
+    ///
+    /// Returns an error if the rounded value is negative or too large to fit.
+    #[track_caller]
+    pub fn to_u64_rounded(&self, mode: RoundingMode) -> Result<u64, ProgramError> {
+        self.round(mode)?.try_to_u64()
+    }
+
+    /// Returns the `Numeric` as an `i64` using the given rounding mode.
+    ///
+    /// Returns an error if the rounded value is too large or too small to fit.
+    #[track_caller]
+    pub fn to_i64_rounded(&self, mode: RoundingMode) -> Result<i64, ProgramError> {
+        self.round(mode)?.try_to_i64()
+    }
+
+    /// Multiplies two `Numeric`s, rounding the result to the nearest representable value in the
+    /// given direction.
+    #[track_caller]
+    pub fn mul_rounded(&self, other: Numeric, mode: RoundingMode) -> Result<Self, ProgramError> {
+        self.mul_div(other, Numeric::ONE, mode)
+    }
+
+    /// Divides two `Numeric`s, rounding the result to the nearest representable value in the
+    /// given direction.
+    #[track_caller]
+    pub fn div_rounded(&self, other: Numeric, mode: RoundingMode) -> Result<Self, ProgramError> {
+        Numeric::ONE.mul_div(*self, other, mode)
+    }
+
+    /// Computes `self * numerator / denominator` with a 256-bit intermediate, so the product
+    /// does not lose precision or overflow before the division.
+    ///
+    /// Returns an error if the denominator is zero or the result does not fit in a `Numeric`.
+    #[track_caller]
+    pub fn mul_div(
+        &self,
+        numerator: Numeric,
+        denominator: Numeric,
+        mode: RoundingMode,
+    ) -> Result<Self, ProgramError> {
+        let c = denominator.to_i80f48().to_bits();
+        if c == 0 {
+            return Err(trace(
+                "Numeric division by zero",
+                ProgramError::InvalidArgument,
+            ));
+        }
+        div_round(
+            self.to_i80f48().to_bits(),
+            numerator.to_i80f48().to_bits(),
+            c,
+            mode,
+        )
+        .map(|bits| Self::from_i80f48(I80F48::from_bits(bits)))
+        .ok_or_else(|| overflow("mul_div"))
+    }
+}
+
+/// Computes `value * numerator / denominator` on integers with a 128-bit intermediate, rounding
+/// in the given direction.
+///
+/// Returns an error if the denominator is zero or the result does not fit in a `u64`.
+#[track_caller]
+pub fn mul_div_u64(
+    value: u64,
+    numerator: u64,
+    denominator: u64,
+    mode: RoundingMode,
+) -> Result<u64, ProgramError> {
+    if denominator == 0 {
+        return Err(trace(
+            "Numeric division by zero",
+            ProgramError::InvalidArgument,
+        ));
+    }
+    div_round(value as i128, numerator as i128, denominator as i128, mode)
+        .and_then(|q| u64::try_from(q).ok())
+        .ok_or_else(|| overflow("mul_div"))
+}
+
+/// Computes `a * b / c` exactly and rounds the quotient in the given direction.
+///
+/// Returns `None` if `c` is zero or the rounded quotient does not fit in an `i128`.
+fn div_round(a: i128, b: i128, c: i128, mode: RoundingMode) -> Option<i128> {
+    if c == 0 {
+        return None;
+    }
+    let negative = (a < 0) ^ (b < 0) ^ (c < 0) && a != 0 && b != 0;
+    let d = c.unsigned_abs();
+    let (hi, lo) = mul_wide(a.unsigned_abs(), b.unsigned_abs());
+    let (q, r) = div_wide(hi, lo, d)?;
+
+    // Round the magnitude away from zero when the mode calls for it.
+    let round_away = r != 0
+        && match mode {
+            RoundingMode::Down => negative,
+            RoundingMode::Up => !negative,
+            RoundingMode::HalfEven => {
+                let half = d - r;
+                r > half || (r == half && q & 1 == 1)
+            }
+        };
+    let q = if round_away { q.checked_add(1)? } else { q };
+
+    if negative {
+        0i128.checked_sub_unsigned(q)
+    } else {
+        i128::try_from(q).ok()
+    }
+}
+
+/// Multiplies two `u128`s into a 256-bit `(hi, lo)` pair.
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let ll = a_lo * b_lo;
+    let lh = a_lo * b_hi;
+    let hl = a_hi * b_lo;
+    let hh = a_hi * b_hi;
+
+    let mid = (ll >> 64) + (lh & MASK) + (hl & MASK);
+    let lo = (ll & MASK) | (mid << 64);
+    let hi = hh + (lh >> 64) + (hl >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// Divides a 256-bit `(hi, lo)` pair by a `u128`, returning the quotient and remainder.
+///
+/// Returns `None` if the quotient does not fit in a `u128`.
+fn div_wide(hi: u128, lo: u128, d: u128) -> Option<(u128, u128)> {
+    if hi == 0 {
+        return Some((lo / d, lo % d));
+    }
+    if hi >= d {
+        return None;
+    }
+
+    // Schoolbook binary long division. Since hi < d, the quotient fits in 128 bits.
+    let mut q = 0u128;
+    let mut r = hi;
+    for i in (0..128).rev() {
+        let carry = r >> 127;
+        r = (r << 1) | ((lo >> i) & 1);
+        q <<= 1;
+        if carry == 1 || r >= d {
+            r = r.wrapping_sub(d);
+            q |= 1;
+        }
+    }
+    Some((q, r))
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+    use num_traits::{One, Zero};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Rounds a rational to an integer using the given rounding mode.
+    fn reference_round(x: &BigRational, mode: RoundingMode) -> BigInt {
+        let floor = x.floor();
+        let frac = x - &floor;
+        let floor = floor.to_integer();
+        if frac.is_zero() {
+            return floor;
+        }
+        match mode {
+            RoundingMode::Down => floor,
+            RoundingMode::Up => floor + 1,
+            RoundingMode::HalfEven => {
+                let half = BigRational::new(BigInt::one(), BigInt::from(2));
+                if frac > half || (frac == half && &floor % 2 != BigInt::zero()) {
+                    floor + 1
+                } else {
+                    floor
+                }
+            }
+        }
+    }
+
+    /// Computes `a * b / c` rounded with a big-rational reference.
+    fn reference_div_round(a: i128, b: i128, c: i128, mode: RoundingMode) -> Option<i128> {
+        let x = BigRational::new(BigInt::from(a) * BigInt::from(b), BigInt::from(c));
+        i128::try_from(reference_round(&x, mode)).ok()
+    }
+
+    fn rounding_mode() -> impl Strategy<Value = RoundingMode> {
+        prop_oneof![
+            Just(RoundingMode::Down),
+            Just(RoundingMode::Up),
+            Just(RoundingMode::HalfEven),
+        ]
+    }
+
+    #[test]
+    fn test_numeric_round() {
+        let cases = [
+            (2.5, RoundingMode::Down, 2),
+            (2.5, RoundingMode::Up, 3),
+            (2.5, RoundingMode::HalfEven, 2),
+            (3.5, RoundingMode::HalfEven, 4),
+            (2.25, RoundingMode::HalfEven, 2),
+            (-2.5, RoundingMode::Down, -3),
+            (-2.5, RoundingMode::Up, -2),
+            (-2.5, RoundingMode::HalfEven, -2),
+            (-2.75, RoundingMode::HalfEven, -3),
+            (4.0, RoundingMode::Up, 4),
+        ];
+        for (value, mode, expected) in cases {
+            let n = Numeric::from_i80f48(I80F48::from_num(value));
+            assert_eq!(n.to_i64_rounded(mode), Ok(expected), "{value} {mode:?}");
+        }
+        assert_eq!(
+            Numeric::from_i80f48(I80F48::MAX).round(RoundingMode::Up),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_numeric_mul_div() {
+        let third = Numeric::ONE.div_rounded(Numeric::from_u64(3), RoundingMode::Down);
+        let third_up = Numeric::ONE.div_rounded(Numeric::from_u64(3), RoundingMode::Up);
+        assert_eq!(
+            third_up.unwrap().to_i80f48().to_bits() - third.unwrap().to_i80f48().to_bits(),
+            1
+        );
+
+        // The product would overflow an I80F48 before the division.
+        let big = Numeric::from_u64(u64::MAX);
+        assert_eq!(
+            big.mul_div(big, big, RoundingMode::Down),
+            Ok(Numeric::from_u64(u64::MAX))
+        );
+        assert_eq!(
+            big.mul_div(Numeric::ONE, Numeric::ZERO, RoundingMode::Down),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            big.mul_div(big, Numeric::ONE, RoundingMode::Down),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+
+        assert_eq!(mul_div_u64(100, 3, 7, RoundingMode::Down), Ok(42));
+        assert_eq!(mul_div_u64(100, 3, 7, RoundingMode::Up), Ok(43));
+        assert_eq!(
+            mul_div_u64(u64::MAX, u64::MAX, u64::MAX, RoundingMode::Up),
+            Ok(u64::MAX)
+        );
+        assert_eq!(
+            mul_div_u64(u64::MAX, 2, 1, RoundingMode::Down),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_div_round_matches_reference(
+            a in any::<i128>(),
+            b in any::<i128>(),
+            c in any::<i128>().prop_filter("nonzero", |c| *c != 0),
+            mode in rounding_mode(),
+        ) {
+            prop_assert_eq!(div_round(a, b, c, mode), reference_div_round(a, b, c, mode));
+        }
+
+        #[test]
+        fn prop_div_round_small_operands(
+            a in -1_000_000i128..1_000_000,
+            b in -1_000i128..1_000,
+            c in (1i128..1_000).prop_flat_map(|c| prop_oneof![Just(c), Just(-c)]),
+            mode in rounding_mode(),
+        ) {
+            prop_assert_eq!(div_round(a, b, c, mode), reference_div_round(a, b, c, mode));
+        }
+
+        #[test]
+        fn prop_mul_div_matches_reference(
+            a in any::<i128>(),
+            b in any::<i128>(),
+            c in any::<i128>().prop_filter("nonzero", |c| *c != 0),
+            mode in rounding_mode(),
+        ) {
+            let n = |bits| Numeric::from_i80f48(I80F48::from_bits(bits));
+            let result = n(a).mul_div(n(b), n(c), mode).ok().map(|r| r.to_i80f48().to_bits());
+            prop_assert_eq!(result, reference_div_round(a, b, c, mode));
+        }
+
+        #[test]
+        fn prop_round_brackets_value(bits in any::<i128>(), mode in rounding_mode()) {
+            let n = Numeric::from_i80f48(I80F48::from_bits(bits));
+            if let Ok(rounded) = n.round(mode) {
+                let diff = (rounded.to_i80f48() - n.to_i80f48()).abs();
+                prop_assert!(diff < I80F48::ONE);
+                match mode {
+                    RoundingMode::Down => prop_assert!(rounded <= n),
+                    RoundingMode::Up => prop_assert!(rounded >= n),
+                    RoundingMode::HalfEven => prop_assert!(diff <= I80F48::from_num(0.5)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::program_error::ProgramError;
+
+use super::{out_of_bounds, PodUnaligned};
+
+/// A Pod-compatible set of `N * 8` bits.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PodBitmap<const N: usize> {
+    bits: [u8; N],
+}
+
+unsafe impl<const N: usize> Zeroable for PodBitmap<N> {}
+unsafe impl<const N: usize> Pod for PodBitmap<N> {}
+unsafe impl<const N: usize> PodUnaligned for PodBitmap<N> {}
+
+impl<const N: usize> PodBitmap<N> {
+    /// The number of bits in the bitmap.
+    pub const CAPACITY: usize = N * 8;
+
+    /// Creates a bitmap with all bits unset.
+    pub const fn new() -> Self {
+        Self { bits: [0; N] }
+    }
+
+    /// Returns true if the bit at `index` is set. Out of bounds bits are never set.
+    pub fn is_set(&self, index: usize) -> bool {
+        index < Self::CAPACITY && self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Sets the bit at `index`.
+    #[track_caller]
+    pub fn set(&mut self, index: usize) -> Result<(), ProgramError> {
+        self.check(index)?;
+        self.bits[index / 8] |= 1 << (index % 8);
+        Ok(())
+    }
+
+    /// Unsets the bit at `index`.
+    #[track_caller]
+    pub fn unset(&mut self, index: usize) -> Result<(), ProgramError> {
+        self.check(index)?;
+        self.bits[index / 8] &= !(1 << (index % 8));
+        Ok(())
+    }
+
+    /// Flips the bit at `index`, returning its new value.
+    #[track_caller]
+    pub fn toggle(&mut self, index: usize) -> Result<bool, ProgramError> {
+        self.check(index)?;
+        self.bits[index / 8] ^= 1 << (index % 8);
+        Ok(self.is_set(index))
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Returns true if no bits are set.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|b| *b == 0)
+    }
+
+    /// Returns true if all bits are set.
+    pub fn is_full(&self) -> bool {
+        self.bits.iter().all(|b| *b == u8::MAX)
+    }
+
+    /// Returns the index of the first unset bit, if any.
+    pub fn first_unset(&self) -> Option<usize> {
+        let (i, byte) = self.bits.iter().enumerate().find(|(_, b)| **b != u8::MAX)?;
+        Some(i * 8 + byte.trailing_ones() as usize)
+    }
+
+    /// Unsets all bits.
+    pub fn clear(&mut self) {
+        self.bits = [0; N];
+    }
+
+    #[track_caller]
+    fn check(&self, index: usize) -> Result<(), ProgramError> {
+        if index >= Self::CAPACITY {
+            return Err(out_of_bounds(index, Self::CAPACITY));
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for PodBitmap<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Debug for PodBitmap<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries((0..Self::CAPACITY).filter(|i| self.is_set(*i)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pod_bitmap() {
+        let mut bitmap = PodBitmap::<2>::new();
+        assert_eq!(std::mem::size_of_val(&bitmap), 2);
+        assert!(bitmap.is_empty());
+
+        bitmap.set(0).unwrap();
+        bitmap.set(9).unwrap();
+        assert!(bitmap.is_set(9));
+        assert!(!bitmap.is_set(16));
+        assert_eq!(bitmap.count_ones(), 2);
+        assert_eq!(bitmap.first_unset(), Some(1));
+        assert_eq!(bitmap.toggle(9), Ok(false));
+        assert_eq!(bitmap.set(16), Err(ProgramError::InvalidArgument));
+
+        // Bits are stored least significant first.
+        bitmap.unset(0).unwrap();
+        bitmap.set(15).unwrap();
+        assert_eq!(bytemuck::bytes_of(&bitmap), &[0, 0x80]);
+        let copy = *bytemuck::from_bytes::<PodBitmap<2>>(&[0xff, 0x7f]);
+        assert_eq!(copy.first_unset(), Some(15));
+        assert!(!copy.is_full());
+
+        bitmap.clear();
+        assert_eq!(bitmap, PodBitmap::default());
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+use crate::{Pod, Zeroable};
+
+/// A Pod-compatible boolean. Any non-zero byte is read as `true`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, Pod, Zeroable)]
+pub struct PodBool(u8);
+
+impl PodBool {
+    /// The `false` value.
+    pub const FALSE: Self = PodBool(0);
+
+    /// The `true` value.
+    pub const TRUE: Self = PodBool(1);
+
+    /// Creates a new `PodBool`.
+    pub const fn new(value: bool) -> Self {
+        PodBool(value as u8)
+    }
+
+    /// Returns the value as a `bool`.
+    pub const fn get(&self) -> bool {
+        self.0 != 0
+    }
+
+    /// Sets the value.
+    pub fn set(&mut self, value: bool) {
+        self.0 = value as u8;
+    }
+}
+
+impl From<bool> for PodBool {
+    fn from(value: bool) -> Self {
+        PodBool::new(value)
+    }
+}
+
+impl From<PodBool> for bool {
+    fn from(value: PodBool) -> Self {
+        value.get()
+    }
+}
+
+impl PartialEq for PodBool {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl Eq for PodBool {}
+
+impl PartialEq<bool> for PodBool {
+    fn eq(&self, other: &bool) -> bool {
+        self.get() == *other
+    }
+}
+
+impl fmt::Debug for PodBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pod_bool() {
+        let mut value = PodBool::from(true);
+        assert_eq!(bytemuck::bytes_of(&value), &[1]);
+        assert!(bool::from(value));
+
+        value.set(false);
+        assert_eq!(value, PodBool::FALSE);
+        assert_eq!(value, false);
+
+        // Any non-zero byte is true.
+        let value = *bytemuck::from_bytes::<PodBool>(&[2]);
+        assert_eq!(value, PodBool::TRUE);
+        assert!(value.get());
+    }
+}
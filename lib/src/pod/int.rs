@@ -0,0 +1,246 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign},
+};
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! pod_int {
+    ($(#[$meta:meta])* $name:ident, $ty:ty) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Pod, Zeroable)]
+        pub struct $name([u8; std::mem::size_of::<$ty>()]);
+
+        impl $name {
+            /// The zero value.
+            pub const ZERO: Self = Self::new(0);
+
+            /// The smallest value.
+            pub const MIN: Self = Self::new(<$ty>::MIN);
+
+            /// The largest value.
+            pub const MAX: Self = Self::new(<$ty>::MAX);
+
+            #[doc = concat!("Creates a new value from a `", stringify!($ty), "`.")]
+            pub const fn new(value: $ty) -> Self {
+                Self(value.to_le_bytes())
+            }
+
+            #[doc = concat!("Returns the value as a `", stringify!($ty), "`.")]
+            pub const fn get(&self) -> $ty {
+                <$ty>::from_le_bytes(self.0)
+            }
+
+            /// Sets the value.
+            pub fn set(&mut self, value: $ty) {
+                self.0 = value.to_le_bytes();
+            }
+
+            /// Checked addition. Returns `None` on overflow.
+            pub fn checked_add(self, rhs: impl Into<$ty>) -> Option<Self> {
+                self.get().checked_add(rhs.into()).map(Self::new)
+            }
+
+            /// Checked subtraction. Returns `None` on overflow.
+            pub fn checked_sub(self, rhs: impl Into<$ty>) -> Option<Self> {
+                self.get().checked_sub(rhs.into()).map(Self::new)
+            }
+
+            /// Checked multiplication. Returns `None` on overflow.
+            pub fn checked_mul(self, rhs: impl Into<$ty>) -> Option<Self> {
+                self.get().checked_mul(rhs.into()).map(Self::new)
+            }
+
+            /// Checked division. Returns `None` if `rhs` is zero or on overflow.
+            pub fn checked_div(self, rhs: impl Into<$ty>) -> Option<Self> {
+                self.get().checked_div(rhs.into()).map(Self::new)
+            }
+
+            /// Saturating addition.
+            pub fn saturating_add(self, rhs: impl Into<$ty>) -> Self {
+                Self::new(self.get().saturating_add(rhs.into()))
+            }
+
+            /// Saturating subtraction.
+            pub fn saturating_sub(self, rhs: impl Into<$ty>) -> Self {
+                Self::new(self.get().saturating_sub(rhs.into()))
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(value: $ty) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+
+        impl PartialEq<$ty> for $name {
+            fn eq(&self, other: &$ty) -> bool {
+                self.get() == *other
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl PartialOrd<$ty> for $name {
+            fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                self.get().partial_cmp(other)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.get(), f)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.get(), f)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.get().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$ty>::deserialize(deserializer).map(Self::new)
+            }
+        }
+
+        pod_int_op!($name, $ty, Add, add, AddAssign, add_assign);
+        pod_int_op!($name, $ty, Sub, sub, SubAssign, sub_assign);
+        pod_int_op!($name, $ty, Mul, mul, MulAssign, mul_assign);
+        pod_int_op!($name, $ty, Div, div, DivAssign, div_assign);
+        pod_int_op!($name, $ty, Rem, rem, RemAssign, rem_assign);
+    };
+}
+
+macro_rules! pod_int_op {
+    ($name:ident, $ty:ty, $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident) => {
+        impl $trait for $name {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                Self::new(self.get().$fn(rhs.get()))
+            }
+        }
+
+        impl $trait<$ty> for $name {
+            type Output = Self;
+
+            fn $fn(self, rhs: $ty) -> Self {
+                Self::new(self.get().$fn(rhs))
+            }
+        }
+
+        impl $assign_trait for $name {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+
+        impl $assign_trait<$ty> for $name {
+            fn $assign_fn(&mut self, rhs: $ty) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+    };
+}
+
+pod_int!(
+    /// A `u16` stored as little-endian bytes, with an alignment of 1.
+    PodU16,
+    u16
+);
+pod_int!(
+    /// A `u32` stored as little-endian bytes, with an alignment of 1.
+    PodU32,
+    u32
+);
+pod_int!(
+    /// A `u64` stored as little-endian bytes, with an alignment of 1.
+    PodU64,
+    u64
+);
+pod_int!(
+    /// A `u128` stored as little-endian bytes, with an alignment of 1.
+    PodU128,
+    u128
+);
+pod_int!(
+    /// An `i64` stored as little-endian bytes, with an alignment of 1.
+    PodI64,
+    i64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Packed {
+        flag: u8,
+        amount: PodU64,
+        total: PodU128,
+    }
+
+    #[test]
+    fn pod_int() {
+        assert_eq!(std::mem::size_of::<Packed>(), 25);
+        assert_eq!(std::mem::align_of::<Packed>(), 1);
+
+        let mut amount = PodU64::from(40);
+        amount += 2;
+        assert_eq!(amount, 42);
+        assert_eq!(u64::from(amount * PodU64::new(2)), 84);
+        assert_eq!(amount.checked_sub(43u64), None);
+        assert_eq!(PodU64::MAX.saturating_add(1u64), PodU64::MAX);
+        assert!(PodU64::new(256) > PodU64::new(255));
+        assert_eq!(bytemuck::bytes_of(&PodU16::new(0x0102)), &[0x02, 0x01]);
+        assert_eq!(PodI64::new(-5).to_string(), "-5");
+    }
+
+    #[test]
+    fn pod_int_round_trip() {
+        macro_rules! round_trip {
+            ($($name:ident: $ty:ty),*) => {
+                $(
+                    for value in [<$ty>::MIN, 0, 1, <$ty>::MAX] {
+                        let pod = $name::from(value);
+                        assert_eq!(<$ty>::from(pod), value);
+                        assert_eq!(bytemuck::bytes_of(&pod), &value.to_le_bytes());
+                        assert_eq!(*bytemuck::from_bytes::<$name>(&value.to_le_bytes()), pod);
+                        let json = serde_json::to_string(&pod).unwrap();
+                        assert_eq!(serde_json::from_str::<$name>(&json).unwrap(), pod);
+                    }
+                )*
+            };
+        }
+
+        round_trip!(PodU16: u16, PodU32: u32, PodU64: u64, PodU128: u128, PodI64: i64);
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::program_error::ProgramError;
+
+use super::{full, read_u64, write_u64, PodUnaligned, HEADER_SIZE};
+use crate::trace;
+
+/// A Pod-compatible map with a fixed capacity of `N` entries.
+///
+/// Keys are kept sorted, so lookups are a binary search. The length is stored in an 8 byte
+/// header, followed by the key and value arrays.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PodMap<K: PodUnaligned + Ord, V: PodUnaligned, const N: usize> {
+    len: [u8; HEADER_SIZE],
+    keys: [K; N],
+    values: [V; N],
+}
+
+unsafe impl<K: PodUnaligned + Ord, V: PodUnaligned, const N: usize> Zeroable for PodMap<K, V, N> {}
+unsafe impl<K: PodUnaligned + Ord, V: PodUnaligned, const N: usize> Pod for PodMap<K, V, N> {}
+unsafe impl<K: PodUnaligned + Ord, V: PodUnaligned, const N: usize> PodUnaligned
+    for PodMap<K, V, N>
+{
+}
+
+impl<K: PodUnaligned + Ord, V: PodUnaligned, const N: usize> PodMap<K, V, N> {
+    /// The maximum number of entries.
+    pub const CAPACITY: usize = N;
+
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::zeroed()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        read_u64(&self.len).min(N)
+    }
+
+    /// Returns true if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the map is at capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the keys in ascending order.
+    pub fn keys(&self) -> &[K] {
+        &self.keys[..self.len()]
+    }
+
+    /// Returns the values, ordered by their keys.
+    pub fn values(&self) -> &[V] {
+        &self.values[..self.len()]
+    }
+
+    /// Returns the values as a mutable slice, ordered by their keys.
+    pub fn values_mut(&mut self) -> &mut [V] {
+        let len = self.len();
+        &mut self.values[..len]
+    }
+
+    /// Returns an iterator over the entries, in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.keys().iter().zip(self.values().iter())
+    }
+
+    /// Returns true if the map contains `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.search(key).is_ok()
+    }
+
+    /// Returns the value for `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.search(key).ok().map(|i| &self.values[i])
+    }
+
+    /// Returns a mutable reference to the value for `key`, if any.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.search(key).ok().map(|i| &mut self.values[i])
+    }
+
+    /// Returns the value for `key`, or an error if the key is missing.
+    #[track_caller]
+    pub fn try_get(&self, key: &K) -> Result<&V, ProgramError> {
+        self.get(key).ok_or_else(missing_key)
+    }
+
+    /// Returns a mutable reference to the value for `key`, or an error if the key is missing.
+    #[track_caller]
+    pub fn try_get_mut(&mut self, key: &K) -> Result<&mut V, ProgramError> {
+        match self.search(key) {
+            Ok(i) => Ok(&mut self.values[i]),
+            Err(_) => Err(missing_key()),
+        }
+    }
+
+    /// Inserts an entry, returning the previous value for the key, if any.
+    ///
+    /// Returns an error if the key is new and the map is full.
+    #[track_caller]
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, ProgramError> {
+        let len = self.len();
+        match self.search(&key) {
+            Ok(i) => Ok(Some(std::mem::replace(&mut self.values[i], value))),
+            Err(i) => {
+                if len == N {
+                    return Err(full(N));
+                }
+                self.keys.copy_within(i..len, i + 1);
+                self.values.copy_within(i..len, i + 1);
+                self.keys[i] = key;
+                self.values[i] = value;
+                write_u64(&mut self.len, len + 1);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Removes the entry for `key`, returning its value, if any.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let len = self.len();
+        let i = self.search(key).ok()?;
+        let value = self.values[i];
+        self.keys.copy_within(i + 1..len, i);
+        self.values.copy_within(i + 1..len, i);
+        self.keys[len - 1] = K::zeroed();
+        self.values[len - 1] = V::zeroed();
+        write_u64(&mut self.len, len - 1);
+        Some(value)
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.keys().binary_search(key)
+    }
+}
+
+impl<K: PodUnaligned + Ord, V: PodUnaligned, const N: usize> Default for PodMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PodUnaligned + Ord + fmt::Debug, V: PodUnaligned + fmt::Debug, const N: usize> fmt::Debug
+    for PodMap<K, V, N>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[track_caller]
+fn missing_key() -> ProgramError {
+    trace("Key not found", ProgramError::InvalidArgument)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PodU32, PodU64};
+
+    #[test]
+    fn pod_map() {
+        let mut map = PodMap::<PodU64, PodU32, 4>::new();
+        assert_eq!(std::mem::size_of_val(&map), 8 + 4 * (8 + 4));
+
+        assert_eq!(map.insert(5.into(), 50.into()), Ok(None));
+        assert_eq!(map.insert(1.into(), 10.into()), Ok(None));
+        assert_eq!(map.insert(3.into(), 30.into()), Ok(None));
+        assert_eq!(map.insert(3.into(), 31.into()), Ok(Some(30.into())));
+        assert_eq!(map.keys(), &[1, 3, 5].map(PodU64::new));
+        assert_eq!(map.values(), &[10, 31, 50].map(PodU32::new));
+        assert_eq!(map.insert(7.into(), 70.into()), Ok(None));
+        assert_eq!(
+            map.insert(4.into(), 40.into()),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        assert_eq!(map.get(&5.into()), Some(&50.into()));
+        assert_eq!(map.try_get(&2.into()), Err(ProgramError::InvalidArgument));
+        *map.try_get_mut(&1.into()).unwrap() += 1;
+        assert_eq!(map.remove(&1.into()), Some(11.into()));
+        assert_eq!(map.remove(&1.into()), None);
+        assert_eq!(map.keys(), &[3, 5, 7].map(PodU64::new));
+        assert!(map.contains_key(&3.into()));
+    }
+}
@@ -0,0 +1,79 @@
+mod bitmap;
+mod boolean;
+mod int;
+mod map;
+mod option;
+mod pubkey;
+mod ring_buffer;
+mod string;
+mod vec;
+
+use bytemuck::Pod;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{trace, Numeric};
+
+pub use bitmap::*;
+pub use boolean::*;
+pub use int::*;
+pub use map::*;
+pub use option::*;
+pub use pubkey::*;
+pub use ring_buffer::*;
+pub use string::*;
+pub use vec::*;
+
+/// Size of the length header at the start of the Pod collections.
+///
+/// The header is stored as a byte array so it doesn't raise the alignment of the collection.
+pub(crate) const HEADER_SIZE: usize = 8;
+
+/// A `Pod` type with an alignment of 1, which can be stored in the Pod collections.
+///
+/// Elements with a larger alignment could leave padding bytes between the header and the
+/// elements, which `Pod` types must not have. Store integers as `PodU64` and the other
+/// little-endian wrappers instead.
+///
+/// # Safety
+///
+/// The type must have an alignment of 1.
+pub unsafe trait PodUnaligned: Pod {}
+
+unsafe impl<T: PodUnaligned, const N: usize> PodUnaligned for [T; N] {}
+
+macro_rules! impl_pod_unaligned {
+    ($($ty:ty),*) => {
+        $(
+            const _: () = assert!(std::mem::align_of::<$ty>() == 1);
+            unsafe impl PodUnaligned for $ty {}
+        )*
+    };
+}
+
+impl_pod_unaligned!(
+    u8, i8, Pubkey, PodBool, PodPubkey, PodU16, PodU32, PodU64, PodU128, PodI64, Numeric
+);
+
+pub(crate) fn read_u64(bytes: &[u8; 8]) -> usize {
+    u64::from_le_bytes(*bytes) as usize
+}
+
+pub(crate) fn write_u64(bytes: &mut [u8; 8], value: usize) {
+    *bytes = (value as u64).to_le_bytes();
+}
+
+#[track_caller]
+pub(crate) fn out_of_bounds(index: usize, len: usize) -> ProgramError {
+    trace(
+        format!("Index out of bounds {} >= {}", index, len).as_str(),
+        ProgramError::InvalidArgument,
+    )
+}
+
+#[track_caller]
+pub(crate) fn full(capacity: usize) -> ProgramError {
+    trace(
+        format!("Collection is full {}", capacity).as_str(),
+        ProgramError::InvalidArgument,
+    )
+}
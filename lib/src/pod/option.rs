@@ -0,0 +1,153 @@
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::program_error::ProgramError;
+
+use super::{PodUnaligned, HEADER_SIZE};
+use crate::trace;
+
+/// A Pod-compatible optional value.
+///
+/// The first byte of an 8 byte header flags whether the value is set. The value bytes are zeroed
+/// when the option is cleared.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PodOption<T: PodUnaligned> {
+    tag: [u8; HEADER_SIZE],
+    value: T,
+}
+
+unsafe impl<T: PodUnaligned> Zeroable for PodOption<T> {}
+unsafe impl<T: PodUnaligned> Pod for PodOption<T> {}
+unsafe impl<T: PodUnaligned> PodUnaligned for PodOption<T> {}
+
+impl<T: PodUnaligned> PodOption<T> {
+    /// Creates an empty option.
+    pub fn none() -> Self {
+        Self::zeroed()
+    }
+
+    /// Creates an option holding `value`.
+    pub fn some(value: T) -> Self {
+        let mut option = Self::none();
+        option.insert(value);
+        option
+    }
+
+    /// Returns true if the option holds a value.
+    pub fn is_some(&self) -> bool {
+        self.tag[0] != 0
+    }
+
+    /// Returns true if the option is empty.
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Returns a reference to the value, if set.
+    pub fn get(&self) -> Option<&T> {
+        self.is_some().then_some(&self.value)
+    }
+
+    /// Returns a mutable reference to the value, if set.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.is_some().then_some(&mut self.value)
+    }
+
+    /// Returns a reference to the value, or an error if the option is empty.
+    #[track_caller]
+    pub fn try_get(&self) -> Result<&T, ProgramError> {
+        self.get()
+            .ok_or_else(|| trace("Option is none", ProgramError::InvalidAccountData))
+    }
+
+    /// Returns a mutable reference to the value, or an error if the option is empty.
+    #[track_caller]
+    pub fn try_get_mut(&mut self) -> Result<&mut T, ProgramError> {
+        if self.is_none() {
+            return Err(trace("Option is none", ProgramError::InvalidAccountData));
+        }
+        Ok(&mut self.value)
+    }
+
+    /// Sets the value, returning a mutable reference to it.
+    pub fn insert(&mut self, value: T) -> &mut T {
+        self.tag[0] = 1;
+        self.value = value;
+        &mut self.value
+    }
+
+    /// Takes the value out of the option, leaving it empty.
+    pub fn take(&mut self) -> Option<T> {
+        let value = self.get().copied();
+        *self = Self::none();
+        value
+    }
+
+    /// Returns the option as a standard `Option`.
+    pub fn to_option(&self) -> Option<T> {
+        self.get().copied()
+    }
+}
+
+impl<T: PodUnaligned> Default for PodOption<T> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<T: PodUnaligned> From<Option<T>> for PodOption<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::some(value),
+            None => Self::none(),
+        }
+    }
+}
+
+impl<T: PodUnaligned> From<PodOption<T>> for Option<T> {
+    fn from(value: PodOption<T>) -> Self {
+        value.to_option()
+    }
+}
+
+impl<T: PodUnaligned + PartialEq> PartialEq for PodOption<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: PodUnaligned + fmt::Debug> fmt::Debug for PodOption<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PodU64, PodVec};
+
+    #[test]
+    fn pod_option() {
+        let mut option = PodOption::<PodU64>::none();
+        assert_eq!(std::mem::size_of_val(&option), 8 + 8);
+        assert_eq!(std::mem::align_of::<PodOption<PodVec<PodU64, 2>>>(), 1);
+        assert!(option.is_none());
+        assert_eq!(option.try_get(), Err(ProgramError::InvalidAccountData));
+
+        *option.insert(41.into()) += 1;
+        assert_eq!(option.get(), Some(&42.into()));
+        assert_eq!(Option::<PodU64>::from(option), Some(42.into()));
+        assert_eq!(PodOption::from(Some(42.into())), option);
+
+        let bytes = bytemuck::bytes_of(&option);
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[8..], &42u64.to_le_bytes());
+
+        // Taken values are zeroed.
+        assert_eq!(option.take(), Some(42.into()));
+        assert!(bytemuck::bytes_of(&option).iter().all(|b| *b == 0));
+        assert_eq!(option, PodOption::from(None));
+    }
+}
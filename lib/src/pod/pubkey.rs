@@ -0,0 +1,124 @@
+use std::{fmt, str::FromStr};
+
+use bytemuck::{Pod, Zeroable};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use solana_program::pubkey::Pubkey;
+
+/// A Pod-compatible public key, serialized as a base58 string.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Pod, Zeroable)]
+pub struct PodPubkey([u8; 32]);
+
+impl PodPubkey {
+    /// Creates a new `PodPubkey` from a `Pubkey`.
+    pub const fn new(pubkey: Pubkey) -> Self {
+        Self(pubkey.to_bytes())
+    }
+
+    /// Returns the public key as a `Pubkey`.
+    pub const fn get(&self) -> Pubkey {
+        Pubkey::new_from_array(self.0)
+    }
+
+    /// Returns a reference to the public key as a `Pubkey`.
+    pub fn as_pubkey(&self) -> &Pubkey {
+        bytemuck::cast_ref(&self.0)
+    }
+
+    /// Returns the raw bytes of the public key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl From<Pubkey> for PodPubkey {
+    fn from(pubkey: Pubkey) -> Self {
+        Self::new(pubkey)
+    }
+}
+
+impl From<PodPubkey> for Pubkey {
+    fn from(pubkey: PodPubkey) -> Self {
+        pubkey.get()
+    }
+}
+
+impl AsRef<[u8]> for PodPubkey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq<Pubkey> for PodPubkey {
+    fn eq(&self, other: &Pubkey) -> bool {
+        self.0 == other.to_bytes()
+    }
+}
+
+impl fmt::Display for PodPubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_pubkey(), f)
+    }
+}
+
+impl fmt::Debug for PodPubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_pubkey(), f)
+    }
+}
+
+impl Serialize for PodPubkey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for PodPubkey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Pubkey::from_str(&s)
+            .map(Self::new)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Packed {
+        flag: u8,
+        authority: PodPubkey,
+    }
+
+    #[test]
+    fn pod_pubkey() {
+        assert_eq!(std::mem::size_of::<Packed>(), 33);
+        assert_eq!(std::mem::align_of::<Packed>(), 1);
+
+        let key = Pubkey::new_unique();
+        let pod = PodPubkey::from(key);
+        assert_eq!(Pubkey::from(pod), key);
+        assert_eq!(pod.as_pubkey(), &key);
+        assert_eq!(pod, key);
+        assert_eq!(pod.as_ref(), key.as_ref());
+        assert_eq!(pod.to_string(), key.to_string());
+        assert_eq!(PodPubkey::default().get(), Pubkey::default());
+
+        // The bytes are the same as the `Pubkey`.
+        let packed = Packed {
+            flag: 1,
+            authority: pod,
+        };
+        let bytes = bytemuck::bytes_of(&packed);
+        assert_eq!(&bytes[1..], &key.to_bytes());
+        assert_eq!(bytemuck::from_bytes::<Packed>(bytes).authority, key);
+
+        let json = serde_json::to_string(&pod).unwrap();
+        assert_eq!(json, format!("\"{}\"", key));
+        assert_eq!(serde_json::from_str::<PodPubkey>(&json).unwrap(), pod);
+        assert!(serde_json::from_str::<PodPubkey>("\"invalid\"").is_err());
+    }
+}
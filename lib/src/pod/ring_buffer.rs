@@ -0,0 +1,172 @@
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::program_error::ProgramError;
+
+use super::{out_of_bounds, PodUnaligned};
+
+/// A Pod-compatible ring buffer holding the latest `N` elements.
+///
+/// The 8 byte header stores the index of the oldest element and the length as little-endian
+/// `u32`s. Pushing to a full buffer overwrites the oldest element.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PodRingBuffer<T: PodUnaligned, const N: usize> {
+    head: [u8; 4],
+    len: [u8; 4],
+    items: [T; N],
+}
+
+unsafe impl<T: PodUnaligned, const N: usize> Zeroable for PodRingBuffer<T, N> {}
+unsafe impl<T: PodUnaligned, const N: usize> Pod for PodRingBuffer<T, N> {}
+unsafe impl<T: PodUnaligned, const N: usize> PodUnaligned for PodRingBuffer<T, N> {}
+
+impl<T: PodUnaligned, const N: usize> PodRingBuffer<T, N> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = N;
+
+    /// Creates an empty ring buffer.
+    pub fn new() -> Self {
+        Self::zeroed()
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        (u32::from_le_bytes(self.len) as usize).min(N)
+    }
+
+    /// Returns true if the buffer has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the buffer is at capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Appends an element, returning the oldest element if it was overwritten.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if N == 0 {
+            return Some(value);
+        }
+        let len = self.len();
+        if len < N {
+            let index = self.physical(len);
+            self.items[index] = value;
+            self.set_len(len + 1);
+            None
+        } else {
+            let head = self.head();
+            let evicted = std::mem::replace(&mut self.items[head], value);
+            self.set_head((head + 1) % N);
+            Some(evicted)
+        }
+    }
+
+    /// Removes and returns the oldest element, if any.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        let head = self.head();
+        let value = std::mem::replace(&mut self.items[head], T::zeroed());
+        self.set_head((head + 1) % N);
+        self.set_len(len - 1);
+        Some(value)
+    }
+
+    /// Returns the oldest element, if any.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the latest element, if any.
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Returns the element at `index`, counting from the oldest, if in bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        (index < self.len()).then(|| &self.items[self.physical(index)])
+    }
+
+    /// Returns the element at `index`, counting from the oldest, or an error if out of bounds.
+    #[track_caller]
+    pub fn try_get(&self, index: usize) -> Result<&T, ProgramError> {
+        self.get(index)
+            .ok_or_else(|| out_of_bounds(index, self.len()))
+    }
+
+    /// Returns an iterator over the elements, from oldest to latest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len()).map(|i| &self.items[self.physical(i)])
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn head(&self) -> usize {
+        u32::from_le_bytes(self.head) as usize % N.max(1)
+    }
+
+    fn physical(&self, index: usize) -> usize {
+        (self.head() + index) % N
+    }
+
+    fn set_head(&mut self, head: usize) {
+        self.head = (head as u32).to_le_bytes();
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.len = (len as u32).to_le_bytes();
+    }
+}
+
+impl<T: PodUnaligned, const N: usize> Default for PodRingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PodUnaligned + fmt::Debug, const N: usize> fmt::Debug for PodRingBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PodU32;
+
+    #[test]
+    fn pod_ring_buffer() {
+        let mut buffer = PodRingBuffer::<PodU32, 3>::new();
+        assert_eq!(std::mem::size_of_val(&buffer), 8 + 3 * 4);
+        assert_eq!(buffer.push(1.into()), None);
+        assert_eq!(buffer.push(2.into()), None);
+        assert_eq!(buffer.push(3.into()), None);
+        assert!(buffer.is_full());
+
+        // Overwrites the oldest element.
+        assert_eq!(buffer.push(4.into()), Some(1.into()));
+        assert_eq!(
+            buffer.iter().map(|v| v.get()).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(buffer.front(), Some(&2.into()));
+        assert_eq!(buffer.back(), Some(&4.into()));
+
+        assert_eq!(buffer.pop_front(), Some(2.into()));
+        assert_eq!(buffer.push(5.into()), None);
+        assert_eq!(
+            buffer.iter().map(|v| v.get()).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(buffer.try_get(3), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::program_error::ProgramError;
+
+use super::PodUnaligned;
+use crate::{trace, ERROR_INVALID_UTF8, ERROR_STRING_TOO_LONG};
+
+/// A Pod-compatible UTF-8 string of at most `N` bytes.
+///
+/// The string is stored null-padded, in the same layout as `string_to_bytes`, so existing
+/// `[u8; N]` fields can be migrated without changing the account data.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PodString<const N: usize> {
+    bytes: [u8; N],
+}
+
+unsafe impl<const N: usize> Zeroable for PodString<N> {}
+unsafe impl<const N: usize> Pod for PodString<N> {}
+unsafe impl<const N: usize> PodUnaligned for PodString<N> {}
+
+impl<const N: usize> PodString<N> {
+    /// The maximum length of the string in bytes.
+    pub const CAPACITY: usize = N;
+
+    /// Creates an empty string.
+    pub const fn new() -> Self {
+        Self { bytes: [0; N] }
+    }
+
+    /// Creates a string, or returns an error if it is longer than `N` bytes.
+    #[track_caller]
+    pub fn try_from_str(s: &str) -> Result<Self, ProgramError> {
+        let mut string = Self::new();
+        string.set(s)?;
+        Ok(string)
+    }
+
+    /// Replaces the contents of the string, or returns an error if it is longer than `N` bytes.
+    #[track_caller]
+    pub fn set(&mut self, s: &str) -> Result<(), ProgramError> {
+        if s.len() > N {
+            return Err(trace(
+                format!("String is too long {} > {}", s.len(), N).as_str(),
+                ProgramError::Custom(ERROR_STRING_TOO_LONG),
+            ));
+        }
+        self.bytes = [0; N];
+        self.bytes[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(())
+    }
+
+    /// Returns the length of the string in bytes, up to the first null byte.
+    pub fn len(&self) -> usize {
+        self.bytes.iter().position(|b| *b == 0).unwrap_or(N)
+    }
+
+    /// Returns true if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bytes of the string, without the null padding.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len()]
+    }
+
+    /// Returns the string as a `&str`, or an error if it is not valid UTF-8.
+    #[track_caller]
+    pub fn as_str(&self) -> Result<&str, ProgramError> {
+        std::str::from_utf8(self.as_bytes()).map_err(|_| {
+            trace(
+                "String is not valid UTF-8",
+                ProgramError::Custom(ERROR_INVALID_UTF8),
+            )
+        })
+    }
+
+    /// Returns the string, replacing any invalid UTF-8 sequences.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(self.as_bytes()).to_string()
+    }
+
+    /// Returns the raw null-padded bytes.
+    pub fn to_bytes(&self) -> [u8; N] {
+        self.bytes
+    }
+}
+
+impl<const N: usize> Default for PodString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for PodString<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for PodString<N> {
+    type Error = ProgramError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from_str(s)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for PodString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for PodString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> fmt::Display for PodString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_lossy())
+    }
+}
+
+impl<const N: usize> fmt::Debug for PodString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pod_string() {
+        let mut s = PodString::<8>::try_from_str("hello").unwrap();
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.as_str(), Ok("hello"));
+        assert_eq!(s, "hello");
+        assert_eq!(&s.to_bytes(), b"hello\0\0\0");
+
+        s.set("hi").unwrap();
+        assert_eq!(&s.to_bytes(), b"hi\0\0\0\0\0\0");
+        assert_eq!(s.to_string(), "hi");
+
+        // Full capacity strings have no null terminator.
+        s.set("12345678").unwrap();
+        assert_eq!(s.len(), 8);
+
+        assert_eq!(
+            PodString::<3>::try_from_str("hello"),
+            Err(ProgramError::Custom(ERROR_STRING_TOO_LONG))
+        );
+        assert_eq!(
+            PodString::from([0xff, 0, 0]).as_str(),
+            Err(ProgramError::Custom(ERROR_INVALID_UTF8))
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::program_error::ProgramError;
+
+use super::{full, out_of_bounds, read_u64, write_u64, PodUnaligned, HEADER_SIZE};
+
+/// A Pod-compatible vector with a fixed capacity of `N` elements.
+///
+/// The length is stored in an 8 byte header, followed by the element array. Elements beyond the
+/// length are kept zeroed.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PodVec<T: PodUnaligned, const N: usize> {
+    len: [u8; HEADER_SIZE],
+    items: [T; N],
+}
+
+unsafe impl<T: PodUnaligned, const N: usize> Zeroable for PodVec<T, N> {}
+unsafe impl<T: PodUnaligned, const N: usize> Pod for PodVec<T, N> {}
+unsafe impl<T: PodUnaligned, const N: usize> PodUnaligned for PodVec<T, N> {}
+
+impl<T: PodUnaligned, const N: usize> PodVec<T, N> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = N;
+
+    /// Creates an empty vector.
+    pub fn new() -> Self {
+        Self::zeroed()
+    }
+
+    /// Creates a vector from a slice.
+    #[track_caller]
+    pub fn try_from_slice(items: &[T]) -> Result<Self, ProgramError> {
+        let mut vec = Self::new();
+        vec.extend_from_slice(items)?;
+        Ok(vec)
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        read_u64(&self.len).min(N)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the vector is at capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.len()]
+    }
+
+    /// Returns the elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        &mut self.items[..len]
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns a mutable iterator over the elements.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Returns a reference to the element at `index`, if in bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    /// Returns a mutable reference to the element at `index`, if in bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    /// Returns a reference to the element at `index`, or an error if out of bounds.
+    #[track_caller]
+    pub fn try_get(&self, index: usize) -> Result<&T, ProgramError> {
+        let len = self.len();
+        self.get(index).ok_or_else(|| out_of_bounds(index, len))
+    }
+
+    /// Returns a mutable reference to the element at `index`, or an error if out of bounds.
+    #[track_caller]
+    pub fn try_get_mut(&mut self, index: usize) -> Result<&mut T, ProgramError> {
+        let len = self.len();
+        if index >= len {
+            return Err(out_of_bounds(index, len));
+        }
+        Ok(&mut self.items[index])
+    }
+
+    /// Appends an element, or returns an error if the vector is full.
+    #[track_caller]
+    pub fn push(&mut self, value: T) -> Result<(), ProgramError> {
+        let len = self.len();
+        if len == N {
+            return Err(full(N));
+        }
+        self.items[len] = value;
+        self.set_len(len + 1);
+        Ok(())
+    }
+
+    /// Appends all elements of a slice, or returns an error if they don't fit.
+    #[track_caller]
+    pub fn extend_from_slice(&mut self, items: &[T]) -> Result<(), ProgramError> {
+        let len = self.len();
+        if len + items.len() > N {
+            return Err(full(N));
+        }
+        self.items[len..len + items.len()].copy_from_slice(items);
+        self.set_len(len + items.len());
+        Ok(())
+    }
+
+    /// Removes and returns the last element, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        let value = std::mem::replace(&mut self.items[len - 1], T::zeroed());
+        self.set_len(len - 1);
+        Some(value)
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it to the right.
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), ProgramError> {
+        let len = self.len();
+        if index > len {
+            return Err(out_of_bounds(index, len));
+        }
+        if len == N {
+            return Err(full(N));
+        }
+        self.items.copy_within(index..len, index + 1);
+        self.items[index] = value;
+        self.set_len(len + 1);
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, shifting all elements after it to the left.
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> Result<T, ProgramError> {
+        let len = self.len();
+        if index >= len {
+            return Err(out_of_bounds(index, len));
+        }
+        let value = self.items[index];
+        self.items.copy_within(index + 1..len, index);
+        self.items[len - 1] = T::zeroed();
+        self.set_len(len - 1);
+        Ok(value)
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) -> Result<T, ProgramError> {
+        let len = self.len();
+        if index >= len {
+            return Err(out_of_bounds(index, len));
+        }
+        let value = self.items[index];
+        self.items[index] = self.items[len - 1];
+        self.items[len - 1] = T::zeroed();
+        self.set_len(len - 1);
+        Ok(value)
+    }
+
+    /// Shortens the vector to `len` elements. Has no effect if `len` is not less than the length.
+    pub fn truncate(&mut self, len: usize) {
+        let current = self.len();
+        if len < current {
+            self.items[len..current].fill(T::zeroed());
+            self.set_len(len);
+        }
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    fn set_len(&mut self, len: usize) {
+        write_u64(&mut self.len, len);
+    }
+}
+
+impl<T: PodUnaligned + PartialEq, const N: usize> PodVec<T, N> {
+    /// Returns true if the vector contains `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        self.as_slice().contains(value)
+    }
+}
+
+impl<T: PodUnaligned, const N: usize> Default for PodVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PodUnaligned + PartialEq, const N: usize> PartialEq for PodVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PodUnaligned + fmt::Debug, const N: usize> fmt::Debug for PodVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: PodUnaligned, const N: usize> IntoIterator for &'a PodVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PodU64;
+
+    #[test]
+    fn pod_vec() {
+        let mut vec = PodVec::<PodU64, 4>::new();
+        assert_eq!(std::mem::size_of_val(&vec), 8 + 4 * 8);
+        assert!(vec.is_empty());
+
+        vec.push(1.into()).unwrap();
+        vec.push(3.into()).unwrap();
+        vec.insert(1, 2.into()).unwrap();
+        vec.push(4.into()).unwrap();
+        assert!(vec.is_full());
+        assert_eq!(vec.as_slice(), &[1, 2, 3, 4].map(PodU64::new));
+        assert_eq!(vec.push(5.into()), Err(ProgramError::InvalidArgument));
+
+        assert_eq!(vec.remove(0), Ok(1.into()));
+        assert_eq!(vec.swap_remove(0), Ok(2.into()));
+        assert_eq!(vec.as_slice(), &[4, 3].map(PodU64::new));
+        assert_eq!(vec.pop(), Some(3.into()));
+        assert!(vec.contains(&4.into()));
+        assert_eq!(vec.try_get(1), Err(ProgramError::InvalidArgument));
+
+        // Removed elements are zeroed.
+        let bytes = bytemuck::bytes_of(&vec);
+        assert_eq!(&bytes[..8], &1u64.to_le_bytes());
+        assert!(bytes[16..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn pod_vec_corrupt_len() {
+        let mut vec = PodVec::<u8, 2>::new();
+        write_u64(&mut vec.len, 100);
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec.push(1), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -1,6 +1,13 @@
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use spl_token_2022::{
+    extension::{
+        cpi_guard, default_account_state, interest_bearing_mint, metadata_pointer, transfer_fee,
+        ExtensionType,
+    },
+    state::AccountState,
+};
 
-use crate::account::invoke_signed_with_bump;
+use crate::{account::invoke_signed_with_bump, MetadataField};
 
 #[inline(always)]
 pub fn create_associated_token_account<'info>(
@@ -31,6 +38,36 @@ pub fn create_associated_token_account<'info>(
     )
 }
 
+/// Creates an associated token account, succeeding without changes if it already exists.
+#[inline(always)]
+pub fn create_associated_token_account_idempotent<'info>(
+    funder_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            funder_info.key,
+            owner_info.key,
+            mint_info.key,
+            token_program.key,
+        ),
+        &[
+            funder_info.clone(),
+            token_account_info.clone(),
+            owner_info.clone(),
+            mint_info.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )
+}
+
 #[inline(always)]
 pub fn close_token_account<'info>(
     account_info: &AccountInfo<'info>,
@@ -272,6 +309,32 @@ pub fn transfer_checked_signed_with_bump<'info>(
     )
 }
 
+#[inline(always)]
+pub fn mint_to<'info>(
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    authority_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::mint_to(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
 #[inline(always)]
 pub fn mint_to_signed<'info>(
     mint_info: &AccountInfo<'info>,
@@ -323,6 +386,34 @@ pub fn mint_to_signed_with_bump<'info>(
     )
 }
 
+#[inline(always)]
+pub fn mint_to_checked<'info>(
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    authority_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::mint_to_checked(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
 #[inline(always)]
 pub fn mint_to_checked_signed<'info>(
     mint_info: &AccountInfo<'info>,
@@ -1162,3 +1253,1511 @@ pub fn get_account_data_size(
         &[mint_info.clone()],
     )
 }
+
+/// Transfers tokens of a mint with the transfer fee extension, asserting the expected fee.
+///
+/// Use [`Mint::transfer_fee`](super::Mint::transfer_fee) to compute the fee. The recipient
+/// receives `amount - fee`, and the fee is withheld in the recipient's token account.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_fee<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &transfer_fee::instruction::transfer_checked_with_fee(
+            token_program.key,
+            from_info.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+            amount,
+            decimals,
+            fee,
+        )?,
+        &[
+            token_program.clone(),
+            from_info.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_fee_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
+    transfer_checked_with_fee_signed_with_bump(
+        authority_info,
+        from_info,
+        mint_info,
+        to_info,
+        token_program,
+        amount,
+        decimals,
+        fee,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_fee_signed_with_bump<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &transfer_fee::instruction::transfer_checked_with_fee(
+            token_program.key,
+            from_info.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+            amount,
+            decimals,
+            fee,
+        )?,
+        &[
+            token_program.clone(),
+            from_info.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Moves the fees withheld in token accounts to their mint. Anyone may harvest withheld fees.
+#[inline(always)]
+pub fn harvest_withheld_tokens_to_mint<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    source_infos: &[AccountInfo<'info>],
+) -> ProgramResult {
+    let sources = source_infos.iter().map(|info| info.key).collect::<Vec<_>>();
+    let mut account_infos = Vec::with_capacity(2 + source_infos.len());
+    account_infos.push(token_program.clone());
+    account_infos.push(mint_info.clone());
+    account_infos.extend_from_slice(source_infos);
+    solana_program::program::invoke(
+        &transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            token_program.key,
+            mint_info.key,
+            &sources,
+        )?,
+        &account_infos,
+    )
+}
+
+/// Withdraws the fees withheld in a mint, signed by its withdraw withheld authority.
+#[inline(always)]
+pub fn withdraw_withheld_tokens_from_mint<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &transfer_fee::instruction::withdraw_withheld_tokens_from_mint(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn withdraw_withheld_tokens_from_mint_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
+    withdraw_withheld_tokens_from_mint_signed_with_bump(
+        authority_info,
+        mint_info,
+        to_info,
+        token_program,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn withdraw_withheld_tokens_from_mint_signed_with_bump<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &transfer_fee::instruction::withdraw_withheld_tokens_from_mint(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Withdraws the fees withheld in token accounts, signed by the mint's withdraw withheld
+/// authority.
+#[inline(always)]
+pub fn withdraw_withheld_tokens_from_accounts<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    source_infos: &[AccountInfo<'info>],
+) -> ProgramResult {
+    let sources = source_infos.iter().map(|info| info.key).collect::<Vec<_>>();
+    solana_program::program::invoke(
+        &transfer_fee::instruction::withdraw_withheld_tokens_from_accounts(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+            &sources,
+        )?,
+        &withdraw_withheld_account_infos(
+            authority_info,
+            mint_info,
+            to_info,
+            token_program,
+            source_infos,
+        ),
+    )
+}
+
+#[inline(always)]
+pub fn withdraw_withheld_tokens_from_accounts_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    source_infos: &[AccountInfo<'info>],
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
+    withdraw_withheld_tokens_from_accounts_signed_with_bump(
+        authority_info,
+        mint_info,
+        to_info,
+        token_program,
+        source_infos,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn withdraw_withheld_tokens_from_accounts_signed_with_bump<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    source_infos: &[AccountInfo<'info>],
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    let sources = source_infos.iter().map(|info| info.key).collect::<Vec<_>>();
+    invoke_signed_with_bump(
+        &transfer_fee::instruction::withdraw_withheld_tokens_from_accounts(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[],
+            &sources,
+        )?,
+        &withdraw_withheld_account_infos(
+            authority_info,
+            mint_info,
+            to_info,
+            token_program,
+            source_infos,
+        ),
+        seeds,
+        bump,
+    )
+}
+
+fn withdraw_withheld_account_infos<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    source_infos: &[AccountInfo<'info>],
+) -> Vec<AccountInfo<'info>> {
+    let mut account_infos = Vec::with_capacity(4 + source_infos.len());
+    account_infos.push(token_program.clone());
+    account_infos.push(mint_info.clone());
+    account_infos.push(to_info.clone());
+    account_infos.push(authority_info.clone());
+    account_infos.extend_from_slice(source_infos);
+    account_infos
+}
+
+/// Transfers tokens of a mint with the transfer hook extension.
+///
+/// The extra accounts required by the mint's transfer hook program are resolved from
+/// `additional_accounts`, which must include the hook program, its extra account metas account
+/// and every account listed there.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from_info.clone(),
+        mint_info.clone(),
+        to_info.clone(),
+        authority_info.clone(),
+        additional_accounts,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
+    transfer_checked_with_hook_signed_with_bump(
+        authority_info,
+        from_info,
+        mint_info,
+        to_info,
+        token_program,
+        additional_accounts,
+        amount,
+        decimals,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook_signed_with_bump<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    // Combine seeds
+    let bump: &[u8] = &[bump];
+    let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
+    combined_seeds.extend_from_slice(seeds);
+    combined_seeds.push(bump);
+
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from_info.clone(),
+        mint_info.clone(),
+        to_info.clone(),
+        authority_info.clone(),
+        additional_accounts,
+        amount,
+        decimals,
+        &[&combined_seeds],
+    )
+}
+
+/// Initializes the mint close authority extension. Must be called before `initialize_mint`.
+#[inline(always)]
+pub fn initialize_mint_close_authority<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    close_authority: Option<&Pubkey>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_mint_close_authority(
+            token_program.key,
+            mint_info.key,
+            close_authority,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Initializes the transfer fee extension. Must be called before `initialize_mint`.
+#[inline(always)]
+pub fn initialize_transfer_fee_config<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    transfer_fee_config_authority: Option<&Pubkey>,
+    withdraw_withheld_authority: Option<&Pubkey>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &transfer_fee::instruction::initialize_transfer_fee_config(
+            token_program.key,
+            mint_info.key,
+            transfer_fee_config_authority,
+            withdraw_withheld_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Sets the transfer fee that takes effect two epochs from now, signed by the mint's transfer
+/// fee config authority.
+#[inline(always)]
+pub fn set_transfer_fee<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &transfer_fee::instruction::set_transfer_fee(
+            token_program.key,
+            mint_info.key,
+            authority_info.key,
+            &[],
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn set_transfer_fee_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
+    set_transfer_fee_signed_with_bump(
+        authority_info,
+        mint_info,
+        token_program,
+        transfer_fee_basis_points,
+        maximum_fee,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn set_transfer_fee_signed_with_bump<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &transfer_fee::instruction::set_transfer_fee(
+            token_program.key,
+            mint_info.key,
+            authority_info.key,
+            &[],
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Initializes the metadata pointer extension. Must be called before `initialize_mint`.
+#[inline(always)]
+pub fn initialize_metadata_pointer<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    authority: Option<Pubkey>,
+    metadata_address: Option<Pubkey>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &metadata_pointer::instruction::initialize(
+            token_program.key,
+            mint_info.key,
+            authority,
+            metadata_address,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Updates the metadata address of a mint, signed by its metadata pointer authority.
+#[inline(always)]
+pub fn update_metadata_pointer<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    metadata_address: Option<Pubkey>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &metadata_pointer::instruction::update(
+            token_program.key,
+            mint_info.key,
+            authority_info.key,
+            &[],
+            metadata_address,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn update_metadata_pointer_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    metadata_address: Option<Pubkey>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
+    update_metadata_pointer_signed_with_bump(
+        authority_info,
+        mint_info,
+        token_program,
+        metadata_address,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn update_metadata_pointer_signed_with_bump<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    metadata_address: Option<Pubkey>,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &metadata_pointer::instruction::update(
+            token_program.key,
+            mint_info.key,
+            authority_info.key,
+            &[],
+            metadata_address,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Initializes the token metadata stored in a mint, signed by its mint authority.
+///
+/// The mint must have a metadata pointer to itself and enough lamports to cover the
+/// reallocation.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_token_metadata<'info>(
+    mint_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    update_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program.key,
+            metadata_info.key,
+            update_authority_info.key,
+            mint_info.key,
+            mint_authority_info.key,
+            name,
+            symbol,
+            uri,
+        ),
+        &[
+            token_program.clone(),
+            metadata_info.clone(),
+            update_authority_info.clone(),
+            mint_info.clone(),
+            mint_authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_token_metadata_signed<'info>(
+    mint_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    update_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    name: String,
+    symbol: String,
+    uri: String,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, mint_authority_info.owner).1;
+    initialize_token_metadata_signed_with_bump(
+        mint_authority_info,
+        metadata_info,
+        update_authority_info,
+        mint_info,
+        token_program,
+        name,
+        symbol,
+        uri,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_token_metadata_signed_with_bump<'info>(
+    mint_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    update_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    name: String,
+    symbol: String,
+    uri: String,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program.key,
+            metadata_info.key,
+            update_authority_info.key,
+            mint_info.key,
+            mint_authority_info.key,
+            name,
+            symbol,
+            uri,
+        ),
+        &[
+            token_program.clone(),
+            metadata_info.clone(),
+            update_authority_info.clone(),
+            mint_info.clone(),
+            mint_authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Updates a field of the token metadata stored in a mint, signed by its update authority.
+///
+/// Growing a field reallocates the metadata account, which must hold enough lamports to stay
+/// rent exempt.
+#[inline(always)]
+pub fn update_token_metadata_field<'info>(
+    update_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    field: MetadataField,
+    value: String,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_metadata_interface::instruction::update_field(
+            token_program.key,
+            metadata_info.key,
+            update_authority_info.key,
+            field,
+            value,
+        ),
+        &[
+            token_program.clone(),
+            metadata_info.clone(),
+            update_authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn update_token_metadata_field_signed<'info>(
+    update_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    field: MetadataField,
+    value: String,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, update_authority_info.owner).1;
+    update_token_metadata_field_signed_with_bump(
+        update_authority_info,
+        metadata_info,
+        token_program,
+        field,
+        value,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn update_token_metadata_field_signed_with_bump<'info>(
+    update_authority_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    field: MetadataField,
+    value: String,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &spl_token_metadata_interface::instruction::update_field(
+            token_program.key,
+            metadata_info.key,
+            update_authority_info.key,
+            field,
+            value,
+        ),
+        &[
+            token_program.clone(),
+            metadata_info.clone(),
+            update_authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Initializes the interest bearing extension with a rate in basis points. Must be called
+/// before `initialize_mint`.
+#[inline(always)]
+pub fn initialize_interest_bearing_mint<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    rate_authority: Option<Pubkey>,
+    rate: i16,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &interest_bearing_mint::instruction::initialize(
+            token_program.key,
+            mint_info.key,
+            rate_authority,
+            rate,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Updates the interest rate of a mint in basis points, signed by its rate authority.
+#[inline(always)]
+pub fn update_interest_rate<'info>(
+    rate_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    rate: i16,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &interest_bearing_mint::instruction::update_rate(
+            token_program.key,
+            mint_info.key,
+            rate_authority_info.key,
+            &[],
+            rate,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            rate_authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn update_interest_rate_signed<'info>(
+    rate_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    rate: i16,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, rate_authority_info.owner).1;
+    update_interest_rate_signed_with_bump(
+        rate_authority_info,
+        mint_info,
+        token_program,
+        rate,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn update_interest_rate_signed_with_bump<'info>(
+    rate_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    rate: i16,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &interest_bearing_mint::instruction::update_rate(
+            token_program.key,
+            mint_info.key,
+            rate_authority_info.key,
+            &[],
+            rate,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            rate_authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Initializes the permanent delegate extension. Must be called before `initialize_mint`.
+#[inline(always)]
+pub fn initialize_permanent_delegate<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    delegate: &Pubkey,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_permanent_delegate(
+            token_program.key,
+            mint_info.key,
+            delegate,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Enables the CPI guard of a token account, signed by its owner.
+///
+/// Token-2022 rejects CPI guard changes made from a cross-program invocation, so the owner
+/// must sign the instruction at the top level of a transaction.
+#[inline(always)]
+pub fn enable_cpi_guard<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &cpi_guard::instruction::enable_cpi_guard(
+            token_program.key,
+            account_info.key,
+            owner_info.key,
+            &[],
+        )?,
+        &[
+            token_program.clone(),
+            account_info.clone(),
+            owner_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn enable_cpi_guard_signed<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, owner_info.owner).1;
+    enable_cpi_guard_signed_with_bump(owner_info, account_info, token_program, seeds, bump)
+}
+
+#[inline(always)]
+pub fn enable_cpi_guard_signed_with_bump<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &cpi_guard::instruction::enable_cpi_guard(
+            token_program.key,
+            account_info.key,
+            owner_info.key,
+            &[],
+        )?,
+        &[
+            token_program.clone(),
+            account_info.clone(),
+            owner_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Disables the CPI guard of a token account, signed by its owner.
+///
+/// Token-2022 rejects CPI guard changes made from a cross-program invocation, so the owner
+/// must sign the instruction at the top level of a transaction.
+#[inline(always)]
+pub fn disable_cpi_guard<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &cpi_guard::instruction::disable_cpi_guard(
+            token_program.key,
+            account_info.key,
+            owner_info.key,
+            &[],
+        )?,
+        &[
+            token_program.clone(),
+            account_info.clone(),
+            owner_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn disable_cpi_guard_signed<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, owner_info.owner).1;
+    disable_cpi_guard_signed_with_bump(owner_info, account_info, token_program, seeds, bump)
+}
+
+#[inline(always)]
+pub fn disable_cpi_guard_signed_with_bump<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &cpi_guard::instruction::disable_cpi_guard(
+            token_program.key,
+            account_info.key,
+            owner_info.key,
+            &[],
+        )?,
+        &[
+            token_program.clone(),
+            account_info.clone(),
+            owner_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Initializes the default account state extension. Must be called before `initialize_mint`.
+#[inline(always)]
+pub fn initialize_default_account_state<'info>(
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    state: AccountState,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &default_account_state::instruction::initialize_default_account_state(
+            token_program.key,
+            mint_info.key,
+            &state,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Updates the state new token accounts of a mint are created in, signed by its freeze
+/// authority.
+#[inline(always)]
+pub fn update_default_account_state<'info>(
+    freeze_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    state: AccountState,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &default_account_state::instruction::update_default_account_state(
+            token_program.key,
+            mint_info.key,
+            freeze_authority_info.key,
+            &[],
+            &state,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            freeze_authority_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn update_default_account_state_signed<'info>(
+    freeze_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    state: AccountState,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, freeze_authority_info.owner).1;
+    update_default_account_state_signed_with_bump(
+        freeze_authority_info,
+        mint_info,
+        token_program,
+        state,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn update_default_account_state_signed_with_bump<'info>(
+    freeze_authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    state: AccountState,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &default_account_state::instruction::update_default_account_state(
+            token_program.key,
+            mint_info.key,
+            freeze_authority_info.key,
+            &[],
+            &state,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            freeze_authority_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+/// Grows a token account to fit the given account extensions, signed by its owner. The payer
+/// funds the additional rent and must also sign.
+#[inline(always)]
+pub fn reallocate<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    extension_types: &[ExtensionType],
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::reallocate(
+            token_program.key,
+            account_info.key,
+            payer_info.key,
+            owner_info.key,
+            &[],
+            extension_types,
+        )?,
+        &[
+            token_program.clone(),
+            account_info.clone(),
+            payer_info.clone(),
+            system_program.clone(),
+            owner_info.clone(),
+        ],
+    )
+}
+
+#[inline(always)]
+pub fn reallocate_signed<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    extension_types: &[ExtensionType],
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, owner_info.owner).1;
+    reallocate_signed_with_bump(
+        owner_info,
+        account_info,
+        payer_info,
+        system_program,
+        token_program,
+        extension_types,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn reallocate_signed_with_bump<'info>(
+    owner_info: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    extension_types: &[ExtensionType],
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    invoke_signed_with_bump(
+        &spl_token_2022::instruction::reallocate(
+            token_program.key,
+            account_info.key,
+            payer_info.key,
+            owner_info.key,
+            &[],
+            extension_types,
+        )?,
+        &[
+            token_program.clone(),
+            account_info.clone(),
+            payer_info.clone(),
+            system_program.clone(),
+            owner_info.clone(),
+        ],
+        seeds,
+        bump,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::{
+        instruction::{AccountMeta, Instruction},
+        system_program,
+    };
+    use spl_token_metadata_interface::instruction::{
+        Initialize, TokenMetadataInstruction, UpdateField,
+    };
+
+    use super::*;
+    use crate::test_utils;
+
+    /// Returns a writable account info for each key, in order.
+    fn account_infos<'a, const N: usize>(
+        keys: &'a [Pubkey; N],
+        lamports: &'a mut [u64; N],
+        data: &'a mut [[u8; 0]; N],
+    ) -> [AccountInfo<'a>; N] {
+        keys.iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(
+                    key,
+                    false,
+                    true,
+                    lamports,
+                    data,
+                    &spl_token_2022::ID,
+                    false,
+                    0,
+                )
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+
+    fn token_instruction(accounts: Vec<AccountMeta>, data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id: spl_token_2022::ID,
+            accounts,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_mint_to() {
+        test_utils::setup();
+        let keys = [
+            spl_token_2022::ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let (mut lamports, mut data) = ([0; 4], [[]; 4]);
+        let [token_program, mint_info, to_info, authority_info] =
+            &account_infos(&keys, &mut lamports, &mut data);
+        let [_, mint, to, authority] = keys;
+
+        mint_to(mint_info, to_info, authority_info, token_program, 100).unwrap();
+        mint_to_checked(mint_info, to_info, authority_info, token_program, 100, 6).unwrap();
+        let accounts = vec![
+            AccountMeta::new(mint, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new_readonly(authority, true),
+        ];
+        assert_eq!(
+            test_utils::invoked(),
+            vec![
+                token_instruction(accounts.clone(), [&[7][..], &100u64.to_le_bytes()].concat()),
+                token_instruction(accounts, [&[14][..], &100u64.to_le_bytes(), &[6]].concat()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_initialize_mint_extensions() {
+        test_utils::setup();
+        let keys = [spl_token_2022::ID, Pubkey::new_unique()];
+        let (mut lamports, mut data) = ([0; 2], [[]; 2]);
+        let [token_program, mint_info] = &account_infos(&keys, &mut lamports, &mut data);
+        let [_, mint] = keys;
+        let authority = Pubkey::new_unique();
+
+        initialize_mint_close_authority(mint_info, token_program, Some(&authority)).unwrap();
+        initialize_transfer_fee_config(mint_info, token_program, Some(&authority), None, 50, 1_000)
+            .unwrap();
+        initialize_metadata_pointer(mint_info, token_program, Some(authority), Some(mint)).unwrap();
+        initialize_interest_bearing_mint(mint_info, token_program, Some(authority), -25).unwrap();
+        initialize_permanent_delegate(mint_info, token_program, &authority).unwrap();
+        initialize_default_account_state(mint_info, token_program, AccountState::Frozen).unwrap();
+
+        // Extensions are initialized on the mint alone, before the mint itself.
+        let authority = authority.as_ref();
+        assert_eq!(
+            test_utils::invoked(),
+            [
+                [&[25, 1][..], authority].concat(),
+                [
+                    &[26, 0, 1][..],
+                    authority,
+                    &[0],
+                    &50u16.to_le_bytes(),
+                    &1_000u64.to_le_bytes(),
+                ]
+                .concat(),
+                [&[39, 0][..], authority, mint.as_ref()].concat(),
+                [&[33, 0][..], authority, &(-25i16).to_le_bytes()].concat(),
+                [&[35][..], authority].concat(),
+                vec![28, 0, AccountState::Frozen as u8],
+            ]
+            .map(|data| token_instruction(vec![AccountMeta::new(mint, false)], data))
+        );
+    }
+
+    #[test]
+    fn test_update_mint_extensions() {
+        test_utils::setup();
+        let keys = [
+            spl_token_2022::ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let (mut lamports, mut data) = ([0; 3], [[]; 3]);
+        let [token_program, mint_info, authority_info] =
+            &account_infos(&keys, &mut lamports, &mut data);
+        let [_, mint, authority] = keys;
+        let seeds: &[&[u8]] = &[b"authority"];
+
+        // Signing with seeds invokes the same instruction.
+        set_transfer_fee(authority_info, mint_info, token_program, 50, 1_000).unwrap();
+        set_transfer_fee_signed_with_bump(
+            authority_info,
+            mint_info,
+            token_program,
+            50,
+            1_000,
+            seeds,
+            255,
+        )
+        .unwrap();
+        update_metadata_pointer(authority_info, mint_info, token_program, None).unwrap();
+        update_metadata_pointer_signed_with_bump(
+            authority_info,
+            mint_info,
+            token_program,
+            None,
+            seeds,
+            255,
+        )
+        .unwrap();
+        update_interest_rate(authority_info, mint_info, token_program, 10).unwrap();
+        update_interest_rate_signed_with_bump(
+            authority_info,
+            mint_info,
+            token_program,
+            10,
+            seeds,
+            255,
+        )
+        .unwrap();
+        update_default_account_state(
+            authority_info,
+            mint_info,
+            token_program,
+            AccountState::Initialized,
+        )
+        .unwrap();
+        update_default_account_state_signed_with_bump(
+            authority_info,
+            mint_info,
+            token_program,
+            AccountState::Initialized,
+            seeds,
+            255,
+        )
+        .unwrap();
+
+        let accounts = vec![
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(authority, true),
+        ];
+        assert_eq!(
+            test_utils::invoked(),
+            [
+                [&[26, 5][..], &50u16.to_le_bytes(), &1_000u64.to_le_bytes()].concat(),
+                [&[39, 1][..], &[0; 32]].concat(),
+                [&[33, 1][..], &10i16.to_le_bytes()].concat(),
+                vec![28, 1, AccountState::Initialized as u8],
+            ]
+            .into_iter()
+            .flat_map(|data| [data.clone(), data])
+            .map(|data| token_instruction(accounts.clone(), data))
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_token_metadata() {
+        test_utils::setup();
+        let keys = [
+            spl_token_2022::ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let (mut lamports, mut data) = ([0; 5], [[]; 5]);
+        let [token_program, mint_authority_info, metadata_info, update_authority_info, mint_info] =
+            &account_infos(&keys, &mut lamports, &mut data);
+        let [_, mint_authority, metadata, update_authority, mint] = keys;
+
+        initialize_token_metadata(
+            mint_authority_info,
+            metadata_info,
+            update_authority_info,
+            mint_info,
+            token_program,
+            "Steel".to_string(),
+            "STL".to_string(),
+            "https://steel.dev".to_string(),
+        )
+        .unwrap();
+        update_token_metadata_field(
+            update_authority_info,
+            metadata_info,
+            token_program,
+            MetadataField::Key("level".to_string()),
+            "1".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            test_utils::invoked(),
+            vec![
+                token_instruction(
+                    vec![
+                        AccountMeta::new(metadata, false),
+                        AccountMeta::new_readonly(update_authority, false),
+                        AccountMeta::new_readonly(mint, false),
+                        AccountMeta::new_readonly(mint_authority, true),
+                    ],
+                    TokenMetadataInstruction::Initialize(Initialize {
+                        name: "Steel".to_string(),
+                        symbol: "STL".to_string(),
+                        uri: "https://steel.dev".to_string(),
+                    })
+                    .pack(),
+                ),
+                token_instruction(
+                    vec![
+                        AccountMeta::new(metadata, false),
+                        AccountMeta::new_readonly(update_authority, true),
+                    ],
+                    TokenMetadataInstruction::UpdateField(UpdateField {
+                        field: MetadataField::Key("level".to_string()),
+                        value: "1".to_string(),
+                    })
+                    .pack(),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cpi_guard() {
+        test_utils::setup();
+        let keys = [
+            spl_token_2022::ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let (mut lamports, mut data) = ([0; 3], [[]; 3]);
+        let [token_program, owner_info, account_info] =
+            &account_infos(&keys, &mut lamports, &mut data);
+        let [_, owner, account] = keys;
+
+        enable_cpi_guard(owner_info, account_info, token_program).unwrap();
+        disable_cpi_guard(owner_info, account_info, token_program).unwrap();
+        let accounts = vec![
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(owner, true),
+        ];
+        assert_eq!(
+            test_utils::invoked(),
+            vec![
+                token_instruction(accounts.clone(), vec![34, 0]),
+                token_instruction(accounts, vec![34, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reallocate() {
+        test_utils::setup();
+        let keys = [
+            spl_token_2022::ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            system_program::ID,
+        ];
+        let (mut lamports, mut data) = ([0; 5], [[]; 5]);
+        let [token_program, owner_info, account_info, payer_info, system_program] =
+            &account_infos(&keys, &mut lamports, &mut data);
+        let [_, owner, account, payer, _] = keys;
+
+        reallocate(
+            owner_info,
+            account_info,
+            payer_info,
+            system_program,
+            token_program,
+            &[ExtensionType::MemoTransfer, ExtensionType::CpiGuard],
+        )
+        .unwrap();
+        assert_eq!(
+            test_utils::invoked(),
+            vec![token_instruction(
+                vec![
+                    AccountMeta::new(account, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                    AccountMeta::new_readonly(owner, true),
+                ],
+                [&[29][..], &8u16.to_le_bytes(), &11u16.to_le_bytes()].concat(),
+            )]
+        );
+    }
+}
@@ -0,0 +1,565 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token_2022::{extension::ExtensionType, state::AccountState};
+
+use crate::{account::allocate_account_signed, trace, AccountInfoValidation};
+
+use super::{
+    initialize_default_account_state, initialize_interest_bearing_mint,
+    initialize_metadata_pointer, initialize_mint_close_authority, initialize_permanent_delegate,
+    initialize_transfer_fee_config, AsSpl,
+};
+
+/// A Token-2022 mint extension and the parameters it is initialized with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MintExtension {
+    MintCloseAuthority {
+        close_authority: Option<Pubkey>,
+    },
+    TransferFeeConfig {
+        transfer_fee_config_authority: Option<Pubkey>,
+        withdraw_withheld_authority: Option<Pubkey>,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+    MetadataPointer {
+        authority: Option<Pubkey>,
+        metadata_address: Option<Pubkey>,
+    },
+    InterestBearingConfig {
+        rate_authority: Option<Pubkey>,
+        rate: i16,
+    },
+    PermanentDelegate {
+        delegate: Pubkey,
+    },
+    DefaultAccountState {
+        state: AccountState,
+    },
+    NonTransferable,
+    TransferHook {
+        authority: Option<Pubkey>,
+        program_id: Option<Pubkey>,
+    },
+}
+
+impl MintExtension {
+    pub fn extension_type(&self) -> ExtensionType {
+        match self {
+            MintExtension::MintCloseAuthority { .. } => ExtensionType::MintCloseAuthority,
+            MintExtension::TransferFeeConfig { .. } => ExtensionType::TransferFeeConfig,
+            MintExtension::MetadataPointer { .. } => ExtensionType::MetadataPointer,
+            MintExtension::InterestBearingConfig { .. } => ExtensionType::InterestBearingConfig,
+            MintExtension::PermanentDelegate { .. } => ExtensionType::PermanentDelegate,
+            MintExtension::DefaultAccountState { .. } => ExtensionType::DefaultAccountState,
+            MintExtension::NonTransferable => ExtensionType::NonTransferable,
+            MintExtension::TransferHook { .. } => ExtensionType::TransferHook,
+        }
+    }
+
+    /// Initializes the extension on an allocated, uninitialized mint.
+    fn initialize<'info>(
+        &self,
+        mint_info: &AccountInfo<'info>,
+        token_program: &AccountInfo<'info>,
+    ) -> ProgramResult {
+        match self {
+            MintExtension::MintCloseAuthority { close_authority } => {
+                initialize_mint_close_authority(mint_info, token_program, close_authority.as_ref())
+            }
+            MintExtension::TransferFeeConfig {
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => initialize_transfer_fee_config(
+                mint_info,
+                token_program,
+                transfer_fee_config_authority.as_ref(),
+                withdraw_withheld_authority.as_ref(),
+                *transfer_fee_basis_points,
+                *maximum_fee,
+            ),
+            MintExtension::MetadataPointer {
+                authority,
+                metadata_address,
+            } => {
+                initialize_metadata_pointer(mint_info, token_program, *authority, *metadata_address)
+            }
+            MintExtension::InterestBearingConfig {
+                rate_authority,
+                rate,
+            } => initialize_interest_bearing_mint(mint_info, token_program, *rate_authority, *rate),
+            MintExtension::PermanentDelegate { delegate } => {
+                initialize_permanent_delegate(mint_info, token_program, delegate)
+            }
+            MintExtension::DefaultAccountState { state } => {
+                initialize_default_account_state(mint_info, token_program, *state)
+            }
+            MintExtension::NonTransferable => solana_program::program::invoke(
+                &spl_token_2022::instruction::initialize_non_transferable_mint(
+                    token_program.key,
+                    mint_info.key,
+                )?,
+                &[token_program.clone(), mint_info.clone()],
+            ),
+            MintExtension::TransferHook {
+                authority,
+                program_id,
+            } => solana_program::program::invoke(
+                &spl_token_2022::extension::transfer_hook::instruction::initialize(
+                    token_program.key,
+                    mint_info.key,
+                    *authority,
+                    *program_id,
+                )?,
+                &[token_program.clone(), mint_info.clone()],
+            ),
+        }
+    }
+}
+
+/// Returns the account size of a mint of the given token program with the given extensions.
+///
+/// Returns an error if `token_program_id` is neither the token program nor Token-2022.
+#[track_caller]
+pub fn mint_space(
+    token_program_id: &Pubkey,
+    extension_types: &[ExtensionType],
+) -> Result<usize, ProgramError> {
+    match *token_program_id {
+        spl_token::ID => {
+            if !extension_types.is_empty() {
+                return Err(trace(
+                    "The token program does not support mint extensions",
+                    ProgramError::InvalidArgument,
+                ));
+            }
+            Ok(spl_token::state::Mint::LEN)
+        }
+        spl_token_2022::ID => {
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extension_types)
+        }
+        _ => Err(invalid_token_program(token_program_id)),
+    }
+}
+
+/// Creates a mint at a PDA of `program_id`.
+///
+/// Allocates the mint with space for the given extensions, initializes each extension and then
+/// initializes the mint. `token_program` must be the token program or Token-2022, and extensions
+/// require Token-2022.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_mint<'info>(
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    decimals: u8,
+    extensions: &[MintExtension],
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, program_id).1;
+    create_mint_with_bump(
+        mint_info,
+        payer,
+        system_program,
+        token_program,
+        mint_authority,
+        freeze_authority,
+        decimals,
+        extensions,
+        program_id,
+        seeds,
+        bump,
+    )
+}
+
+/// Creates a mint at a PDA of `program_id` with user-provided bump, which must be canonical.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_mint_with_bump<'info>(
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    decimals: u8,
+    extensions: &[MintExtension],
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    let extension_types = extensions
+        .iter()
+        .map(MintExtension::extension_type)
+        .collect::<Vec<_>>();
+    let space = mint_space(token_program.key, &extension_types)?;
+
+    allocate_pda(
+        mint_info,
+        payer,
+        system_program,
+        space,
+        token_program.key,
+        program_id,
+        seeds,
+        bump,
+    )?;
+
+    // Extensions must be initialized before the mint.
+    for extension in extensions {
+        extension.initialize(mint_info, token_program)?;
+    }
+
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_program.key,
+            mint_info.key,
+            mint_authority,
+            freeze_authority,
+            decimals,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )
+}
+
+/// Returns the account size of a token account of the given token program for a mint with the
+/// given extensions.
+///
+/// Token-2022 accounts include the account extensions the mint requires and `ImmutableOwner`.
+/// Returns an error if `token_program_id` is neither the token program nor Token-2022.
+#[track_caller]
+pub fn token_account_space(
+    token_program_id: &Pubkey,
+    mint_extension_types: &[ExtensionType],
+) -> Result<usize, ProgramError> {
+    match *token_program_id {
+        spl_token::ID => Ok(spl_token::state::Account::LEN),
+        spl_token_2022::ID => {
+            let mut extension_types =
+                ExtensionType::get_required_init_account_extensions(mint_extension_types);
+            if !extension_types.contains(&ExtensionType::ImmutableOwner) {
+                extension_types.push(ExtensionType::ImmutableOwner);
+            }
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+                &extension_types,
+            )
+        }
+        _ => Err(invalid_token_program(token_program_id)),
+    }
+}
+
+/// Creates a token account at a PDA of `program_id`.
+///
+/// Allocates the account with space for the extensions the mint requires and initializes it for
+/// `owner`. `token_program` must be the token program or Token-2022. Token-2022 accounts are
+/// created with an immutable owner.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_token_account<'info>(
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, program_id).1;
+    create_token_account_with_bump(
+        token_account_info,
+        mint_info,
+        payer,
+        system_program,
+        token_program,
+        owner,
+        program_id,
+        seeds,
+        bump,
+    )
+}
+
+/// Creates a token account at a PDA of `program_id` with user-provided bump, which must be
+/// canonical.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_token_account_with_bump<'info>(
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    let mint = mint_info.as_mint()?;
+    let space = token_account_space(token_program.key, &mint.extension_types()?)?;
+    allocate_pda(
+        token_account_info,
+        payer,
+        system_program,
+        space,
+        token_program.key,
+        program_id,
+        seeds,
+        bump,
+    )?;
+
+    // Account extensions must be initialized before the account.
+    if token_program.key.ne(&spl_token::ID) {
+        solana_program::program::invoke(
+            &spl_token_2022::instruction::initialize_immutable_owner(
+                token_program.key,
+                token_account_info.key,
+            )?,
+            &[token_program.clone(), token_account_info.clone()],
+        )?;
+    }
+
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            token_program.key,
+            token_account_info.key,
+            mint_info.key,
+            owner,
+        )?,
+        &[
+            token_program.clone(),
+            token_account_info.clone(),
+            mint_info.clone(),
+        ],
+    )
+}
+
+#[track_caller]
+pub(crate) fn invalid_token_program(token_program_id: &Pubkey) -> ProgramError {
+    trace(
+        format!("Invalid token program {}", token_program_id).as_str(),
+        ProgramError::IncorrectProgramId,
+    )
+}
+
+/// Allocates a PDA of `program_id` owned by the token program.
+#[allow(clippy::too_many_arguments)]
+fn allocate_pda<'info>(
+    target_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    // Validate seeds.
+    target_account.has_seeds_with_bump(seeds, program_id, bump)?;
+
+    // Combine seeds
+    let bump: &[u8] = &[bump];
+    let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
+    combined_seeds.extend_from_slice(seeds);
+    combined_seeds.push(bump);
+
+    allocate_account_signed(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        &combined_seeds,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mint_space() {
+        assert_eq!(mint_space(&spl_token::ID, &[]), Ok(82));
+        assert_eq!(mint_space(&spl_token_2022::ID, &[]), Ok(82));
+        assert!(mint_space(&spl_token::ID, &[ExtensionType::TransferFeeConfig]).is_err());
+        assert_eq!(
+            mint_space(&Pubkey::new_unique(), &[]),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        let extensions = [
+            MintExtension::TransferFeeConfig {
+                transfer_fee_config_authority: None,
+                withdraw_withheld_authority: None,
+                transfer_fee_basis_points: 50,
+                maximum_fee: 1_000,
+            },
+            MintExtension::NonTransferable,
+        ];
+        let types = extensions
+            .iter()
+            .map(MintExtension::extension_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            mint_space(&spl_token_2022::ID, &types),
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::NonTransferable,
+            ])
+        );
+        assert!(mint_space(&spl_token_2022::ID, &types).unwrap() > 165);
+    }
+
+    #[test]
+    fn test_create_mint_invalid_token_program() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"mint"];
+        let (address, bump) = Pubkey::find_program_address(seeds, &program_id);
+        let token_program_id = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut token_program_lamports = 0;
+        let mut data = [];
+        let mut token_program_data = [];
+        let info = AccountInfo::new(
+            &address,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &solana_program::system_program::ID,
+            false,
+            0,
+        );
+        let token_program = AccountInfo::new(
+            &token_program_id,
+            false,
+            false,
+            &mut token_program_lamports,
+            &mut token_program_data,
+            &program_id,
+            true,
+            0,
+        );
+
+        // The token program is checked before the mint is allocated.
+        assert_eq!(
+            create_mint_with_bump(
+                &info,
+                &info,
+                &info,
+                &token_program,
+                &address,
+                None,
+                6,
+                &[],
+                &program_id,
+                seeds,
+                bump,
+            ),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_create_token_account_invalid_token_program() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"vault"];
+        let (address, bump) = Pubkey::find_program_address(seeds, &program_id);
+        let mint_address = Pubkey::new_unique();
+        let token_program_id = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut mint_lamports = 0;
+        let mut token_program_lamports = 0;
+        let mut data = [];
+        let mut mint_data = [0; spl_token::state::Mint::LEN];
+        let mut token_program_data = [];
+        spl_token::state::Mint {
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut mint_data);
+        let info = AccountInfo::new(
+            &address,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &solana_program::system_program::ID,
+            false,
+            0,
+        );
+        let mint_info = AccountInfo::new(
+            &mint_address,
+            false,
+            false,
+            &mut mint_lamports,
+            &mut mint_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let token_program = AccountInfo::new(
+            &token_program_id,
+            false,
+            false,
+            &mut token_program_lamports,
+            &mut token_program_data,
+            &program_id,
+            true,
+            0,
+        );
+
+        // The token program is checked before the account is allocated.
+        assert_eq!(
+            create_token_account_with_bump(
+                &info,
+                &mint_info,
+                &info,
+                &info,
+                &token_program,
+                &address,
+                &program_id,
+                seeds,
+                bump,
+            ),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_token_account_space() {
+        assert_eq!(token_account_space(&spl_token::ID, &[]), Ok(165));
+        assert_eq!(
+            token_account_space(&Pubkey::new_unique(), &[]),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            token_account_space(&spl_token_2022::ID, &[]),
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+                ExtensionType::ImmutableOwner
+            ])
+        );
+        assert_eq!(
+            token_account_space(
+                &spl_token_2022::ID,
+                &[
+                    ExtensionType::TransferFeeConfig,
+                    ExtensionType::NonTransferable
+                ]
+            ),
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+                ExtensionType::TransferFeeAmount,
+                ExtensionType::NonTransferableAccount,
+                ExtensionType::ImmutableOwner,
+            ])
+        );
+    }
+}
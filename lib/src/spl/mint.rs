@@ -1,15 +1,38 @@
-use solana_program::{program_option::COption, pubkey::Pubkey};
+use solana_program::{
+    clock::{Clock, Epoch},
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use spl_token_2022::{
+    extension::{
+        default_account_state::DefaultAccountState, interest_bearing_mint::InterestBearingConfig,
+        metadata_pointer::MetadataPointer, non_transferable::NonTransferable,
+        permanent_delegate::PermanentDelegate, transfer_fee::TransferFeeConfig,
+        transfer_hook::TransferHook, BaseStateWithExtensions, Extension, ExtensionType,
+        StateWithExtensions,
+    },
+    state::AccountState,
+};
 
-pub enum Mint {
+use crate::trace;
+
+use super::assert_no_extensions;
+
+/// A parsed mint. Token-2022 extensions are read from the account data on demand.
+pub enum Mint<'a> {
     V0(spl_token::state::Mint),
     V1(spl_token_2022::state::Mint),
+    V1WithExtensions(StateWithExtensions<'a, spl_token_2022::state::Mint>),
 }
 
-impl Mint {
+impl Mint<'_> {
     pub fn mint_authority(&self) -> COption<Pubkey> {
         match self {
             Mint::V0(mint) => mint.mint_authority,
             Mint::V1(mint) => mint.mint_authority,
+            Mint::V1WithExtensions(mint) => mint.base.mint_authority,
         }
     }
 
@@ -17,6 +40,7 @@ impl Mint {
         match self {
             Mint::V0(mint) => mint.supply,
             Mint::V1(mint) => mint.supply,
+            Mint::V1WithExtensions(mint) => mint.base.supply,
         }
     }
 
@@ -24,6 +48,7 @@ impl Mint {
         match self {
             Mint::V0(mint) => mint.decimals,
             Mint::V1(mint) => mint.decimals,
+            Mint::V1WithExtensions(mint) => mint.base.decimals,
         }
     }
 
@@ -31,6 +56,7 @@ impl Mint {
         match self {
             Mint::V0(mint) => mint.is_initialized,
             Mint::V1(mint) => mint.is_initialized,
+            Mint::V1WithExtensions(mint) => mint.base.is_initialized,
         }
     }
 
@@ -38,6 +64,169 @@ impl Mint {
         match self {
             Mint::V0(mint) => mint.freeze_authority,
             Mint::V1(mint) => mint.freeze_authority,
+            Mint::V1WithExtensions(mint) => mint.base.freeze_authority,
+        }
+    }
+
+    /// Returns the Token-2022 extensions on the mint, or an error if the extension data is
+    /// malformed.
+    #[track_caller]
+    pub fn extension_types(&self) -> Result<Vec<ExtensionType>, ProgramError> {
+        match self {
+            Mint::V1WithExtensions(mint) => mint
+                .get_extension_types()
+                .map_err(|err| trace("Mint extension data is invalid", err)),
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Returns true if the mint has the given Token-2022 extension.
+    #[track_caller]
+    pub fn has_extension(&self, extension_type: ExtensionType) -> Result<bool, ProgramError> {
+        Ok(self.extension_types()?.contains(&extension_type))
+    }
+
+    /// Returns the given Token-2022 extension if the mint has it, or an error if the extension
+    /// data is malformed.
+    #[track_caller]
+    pub fn get_extension<E: Extension + bytemuck::Pod>(&self) -> Result<Option<&E>, ProgramError> {
+        match self {
+            Mint::V1WithExtensions(mint) if self.has_extension(E::TYPE)? => mint
+                .get_extension::<E>()
+                .map(Some)
+                .map_err(|err| trace("Mint extension data is invalid", err)),
+            _ => Ok(None),
+        }
+    }
+
+    #[track_caller]
+    pub fn transfer_fee_config(&self) -> Result<Option<&TransferFeeConfig>, ProgramError> {
+        self.get_extension()
+    }
+
+    /// Returns the fee withheld when transferring `amount` in the current epoch.
+    #[track_caller]
+    pub fn transfer_fee(&self, amount: u64) -> Result<u64, ProgramError> {
+        self.transfer_fee_for_epoch(amount, Clock::get()?.epoch)
+    }
+
+    /// Returns the fee withheld when transferring `amount` in the given epoch.
+    #[track_caller]
+    pub fn transfer_fee_for_epoch(&self, amount: u64, epoch: Epoch) -> Result<u64, ProgramError> {
+        match self.transfer_fee_config()? {
+            Some(config) => config
+                .calculate_epoch_fee(epoch, amount)
+                .ok_or_else(fee_overflow),
+            None => Ok(0),
+        }
+    }
+
+    /// Returns the amount the recipient receives when `amount` is transferred in the current epoch.
+    #[track_caller]
+    pub fn amount_after_fee(&self, amount: u64) -> Result<u64, ProgramError> {
+        self.amount_after_fee_for_epoch(amount, Clock::get()?.epoch)
+    }
+
+    /// Returns the amount the recipient receives when `amount` is transferred in the given epoch.
+    #[track_caller]
+    pub fn amount_after_fee_for_epoch(
+        &self,
+        amount: u64,
+        epoch: Epoch,
+    ) -> Result<u64, ProgramError> {
+        amount
+            .checked_sub(self.transfer_fee_for_epoch(amount, epoch)?)
+            .ok_or_else(fee_overflow)
+    }
+
+    /// Returns the amount to transfer in the current epoch so the recipient receives `amount`.
+    #[track_caller]
+    pub fn amount_before_fee(&self, amount: u64) -> Result<u64, ProgramError> {
+        self.amount_before_fee_for_epoch(amount, Clock::get()?.epoch)
+    }
+
+    /// Returns the amount to transfer in the given epoch so the recipient receives `amount`.
+    #[track_caller]
+    pub fn amount_before_fee_for_epoch(
+        &self,
+        amount: u64,
+        epoch: Epoch,
+    ) -> Result<u64, ProgramError> {
+        match self.transfer_fee_config()? {
+            Some(config) => config
+                .get_epoch_fee(epoch)
+                .calculate_pre_fee_amount(amount)
+                .ok_or_else(fee_overflow),
+            None => Ok(amount),
         }
     }
+
+    #[track_caller]
+    pub fn interest_bearing_config(&self) -> Result<Option<&InterestBearingConfig>, ProgramError> {
+        self.get_extension()
+    }
+
+    #[track_caller]
+    pub fn transfer_hook(&self) -> Result<Option<&TransferHook>, ProgramError> {
+        self.get_extension()
+    }
+
+    /// Returns the transfer hook program, if the mint has one set.
+    #[track_caller]
+    pub fn transfer_hook_program_id(&self) -> Result<Option<Pubkey>, ProgramError> {
+        Ok(self
+            .transfer_hook()?
+            .and_then(|hook| Option::<Pubkey>::from(hook.program_id)))
+    }
+
+    #[track_caller]
+    pub fn permanent_delegate(&self) -> Result<Option<Pubkey>, ProgramError> {
+        Ok(self
+            .get_extension::<PermanentDelegate>()?
+            .and_then(|ext| Option::<Pubkey>::from(ext.delegate)))
+    }
+
+    #[track_caller]
+    pub fn is_non_transferable(&self) -> Result<bool, ProgramError> {
+        Ok(self.get_extension::<NonTransferable>()?.is_some())
+    }
+
+    #[track_caller]
+    pub fn metadata_pointer(&self) -> Result<Option<&MetadataPointer>, ProgramError> {
+        self.get_extension()
+    }
+
+    /// Returns the metadata account, if the mint has a metadata pointer set.
+    #[track_caller]
+    pub fn metadata_address(&self) -> Result<Option<Pubkey>, ProgramError> {
+        Ok(self
+            .metadata_pointer()?
+            .and_then(|ext| Option::<Pubkey>::from(ext.metadata_address)))
+    }
+
+    /// Returns the state new token accounts for the mint are created in.
+    #[track_caller]
+    pub fn default_account_state(&self) -> Result<Option<AccountState>, ProgramError> {
+        Ok(self
+            .get_extension::<DefaultAccountState>()?
+            .and_then(|ext| AccountState::try_from(ext.state).ok()))
+    }
+
+    /// Returns an error if the mint has any of the given Token-2022 extensions.
+    #[track_caller]
+    pub fn assert_no_extensions(
+        &self,
+        extension_types: &[ExtensionType],
+    ) -> Result<&Self, ProgramError> {
+        assert_no_extensions("Mint", &self.extension_types()?, extension_types)?;
+        Ok(self)
+    }
+}
+
+#[track_caller]
+fn fee_overflow() -> ProgramError {
+    trace(
+        "Transfer fee calculation overflowed",
+        ProgramError::ArithmeticOverflow,
+    )
 }
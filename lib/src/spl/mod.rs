@@ -1,9 +1,15 @@
 pub mod cpi;
+pub mod create;
 pub mod mint;
+pub mod native;
 pub mod token;
+pub mod transfer_hook;
 pub mod validation;
 
 pub use cpi::*;
+pub use create::*;
 pub use mint::*;
+pub use native::*;
 pub use token::*;
+pub use transfer_hook::*;
 pub use validation::*;
@@ -0,0 +1,513 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::{account::AccountValidation, trace, AccountInfoValidation, LamportTransfer};
+
+use super::{
+    close_token_account, close_token_account_signed_with_bump, create::invalid_token_program,
+    create_associated_token_account_idempotent, create_token_account_with_bump, sync_native, AsSpl,
+};
+
+/// Creates the wrapped SOL associated token account of `owner_info`, succeeding without changes
+/// if it already exists.
+#[inline(always)]
+pub fn create_native_associated_token_account<'info>(
+    funder_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    assert_native_mint(mint_info, token_program)?;
+    create_associated_token_account_idempotent(
+        funder_info,
+        owner_info,
+        token_account_info,
+        mint_info,
+        system_program,
+        token_program,
+        associated_token_program,
+    )
+}
+
+/// Creates a wrapped SOL token account at a PDA of `program_id`, such as a temporary account to
+/// unwrap SOL through.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_native_token_account<'info>(
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, program_id).1;
+    create_native_token_account_with_bump(
+        token_account_info,
+        mint_info,
+        payer,
+        system_program,
+        token_program,
+        owner,
+        program_id,
+        seeds,
+        bump,
+    )
+}
+
+/// Creates a wrapped SOL token account at a PDA of `program_id` with user-provided bump.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn create_native_token_account_with_bump<'info>(
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    assert_native_mint(mint_info, token_program)?;
+    create_token_account_with_bump(
+        token_account_info,
+        mint_info,
+        payer,
+        system_program,
+        token_program,
+        owner,
+        program_id,
+        seeds,
+        bump,
+    )
+}
+
+/// Wraps lamports into a native token account, such as a wrapped SOL associated token account.
+///
+/// Lamports are moved with a system transfer if `from_info` is a system account, which must sign,
+/// or directly if it is owned by the executing program.
+#[inline(always)]
+pub fn wrap_sol<'info>(
+    from_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> ProgramResult {
+    assert_native(token_account_info)?;
+    move_lamports(from_info, token_account_info, system_program, amount, &[])?;
+    sync_native(token_account_info)
+}
+
+/// Wraps lamports from a system owned PDA of `program_id` into a native token account.
+///
+/// The PDA signs the system transfer. A PDA owned by the executing program is debited directly
+/// and needs no signature, so `wrap_sol` is enough for it.
+#[inline(always)]
+pub fn wrap_sol_signed<'info>(
+    from_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, program_id).1;
+    wrap_sol_signed_with_bump(
+        from_info,
+        token_account_info,
+        system_program,
+        amount,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn wrap_sol_signed_with_bump<'info>(
+    from_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    assert_native(token_account_info)?;
+
+    // Combine seeds
+    let bump: &[u8] = &[bump];
+    let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
+    combined_seeds.extend_from_slice(seeds);
+    combined_seeds.push(bump);
+
+    move_lamports(
+        from_info,
+        token_account_info,
+        system_program,
+        amount,
+        &[&combined_seeds],
+    )?;
+    sync_native(token_account_info)
+}
+
+/// Unwraps a native token account by closing it, sending all of its lamports to the recipient.
+#[inline(always)]
+pub fn unwrap_sol<'info>(
+    owner_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    recipient_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    assert_native(token_account_info)?;
+    close_token_account(
+        token_account_info,
+        recipient_info,
+        owner_info,
+        token_program,
+    )
+}
+
+/// Unwraps a native token account owned by a PDA of `program_id`.
+#[inline(always)]
+pub fn unwrap_sol_signed<'info>(
+    owner_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    recipient_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let bump = Pubkey::find_program_address(seeds, program_id).1;
+    unwrap_sol_signed_with_bump(
+        owner_info,
+        token_account_info,
+        recipient_info,
+        token_program,
+        seeds,
+        bump,
+    )
+}
+
+#[inline(always)]
+pub fn unwrap_sol_signed_with_bump<'info>(
+    owner_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    recipient_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
+    assert_native(token_account_info)?;
+    close_token_account_signed_with_bump(
+        token_account_info,
+        recipient_info,
+        owner_info,
+        token_program,
+        seeds,
+        bump,
+    )
+}
+
+#[track_caller]
+fn assert_native_mint(mint_info: &AccountInfo, token_program: &AccountInfo) -> ProgramResult {
+    let native_mint = match *token_program.key {
+        spl_token::ID => spl_token::native_mint::ID,
+        spl_token_2022::ID => spl_token_2022::native_mint::ID,
+        _ => return Err(invalid_token_program(token_program.key)),
+    };
+    mint_info.has_address(&native_mint)?;
+    Ok(())
+}
+
+#[track_caller]
+fn assert_native(token_account_info: &AccountInfo) -> ProgramResult {
+    token_account_info
+        .as_token_account()?
+        .assert_msg(|t| t.is_native().is_some(), "Token account is not native")?;
+    Ok(())
+}
+
+#[track_caller]
+fn move_lamports<'info>(
+    from_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    if from_info.owner.eq(&system_program::ID) {
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::transfer(from_info.key, to_info.key, amount),
+            &[from_info.clone(), to_info.clone(), system_program.clone()],
+            signer_seeds,
+        )
+    } else {
+        if from_info.lamports() < amount {
+            return Err(trace(
+                "Insufficient lamports to wrap",
+                ProgramError::InsufficientFunds,
+            ));
+        }
+        from_info.send(amount, to_info);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::{program_option::COption, program_pack::Pack};
+    use spl_token::state::{Account, AccountState};
+
+    use super::*;
+    use crate::test_utils;
+
+    fn token_account(is_native: COption<u64>) -> Vec<u8> {
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint: spl_token::native_mint::ID,
+            owner: Pubkey::new_unique(),
+            amount: 0,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    #[test]
+    fn test_assert_native() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut native = token_account(COption::Some(2_039_280));
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut native,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        assert!(assert_native(&info).is_ok());
+
+        let mut lamports = 0;
+        let mut other = token_account(COption::None);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut other,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        assert!(assert_native(&info).is_err());
+        assert!(unwrap_sol(&info, &info, &info, &info).is_err());
+    }
+
+    #[test]
+    fn test_wrap_sol() {
+        test_utils::setup();
+        let from = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut from_lamports = 5_000_000;
+        let mut lamports = 2_039_280;
+        let mut from_data = [];
+        let mut native = token_account(COption::Some(2_039_280));
+        let mut system_lamports = 0;
+        let mut system_data = [];
+        let from_info = AccountInfo::new(
+            &from,
+            true,
+            true,
+            &mut from_lamports,
+            &mut from_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut native,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let system_program = AccountInfo::new(
+            &system_program::ID,
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &solana_program::bpf_loader::ID,
+            true,
+            0,
+        );
+
+        // Lamports are transferred into the account, which is then synced.
+        wrap_sol(&from_info, &info, &system_program, 1_000_000).unwrap();
+        assert_eq!(from_info.lamports(), 4_000_000);
+        assert_eq!(info.lamports(), 3_039_280);
+        assert_eq!(
+            test_utils::invoked(),
+            vec![
+                solana_program::system_instruction::transfer(&from, &key, 1_000_000),
+                spl_token_2022::instruction::sync_native(&spl_token::ID, &key).unwrap(),
+            ]
+        );
+
+        // Program owned accounts are debited directly.
+        test_utils::setup();
+        let program_id = Pubkey::new_unique();
+        let mut vault_lamports = 1_500_000;
+        let mut vault_data = [];
+        let vault_info = AccountInfo::new(
+            &from,
+            false,
+            true,
+            &mut vault_lamports,
+            &mut vault_data,
+            &program_id,
+            false,
+            0,
+        );
+        wrap_sol(&vault_info, &info, &system_program, 1_000_000).unwrap();
+        assert_eq!(vault_info.lamports(), 500_000);
+        assert_eq!(info.lamports(), 4_039_280);
+        assert_eq!(test_utils::invoked().len(), 1);
+        assert_eq!(
+            wrap_sol(&vault_info, &info, &system_program, 1_000_000),
+            Err(ProgramError::InsufficientFunds)
+        );
+
+        // System owned PDAs of the program sign the transfer.
+        test_utils::setup();
+        let seeds: &[&[u8]] = &[b"vault"];
+        let (pda, bump) = Pubkey::find_program_address(seeds, &program_id);
+        let mut pda_lamports = 1_000_000;
+        let mut pda_data = [];
+        let pda_info = AccountInfo::new(
+            &pda,
+            false,
+            true,
+            &mut pda_lamports,
+            &mut pda_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        wrap_sol_signed(
+            &pda_info,
+            &info,
+            &system_program,
+            1_000_000,
+            &program_id,
+            seeds,
+        )
+        .unwrap();
+        assert_eq!(pda_info.lamports(), 0);
+        assert_eq!(info.lamports(), 5_039_280);
+        assert_eq!(
+            test_utils::invoked()[0],
+            solana_program::system_instruction::transfer(&pda, &key, 1_000_000)
+        );
+        assert_eq!(
+            test_utils::signer_seeds()[0],
+            vec![vec![b"vault".to_vec(), vec![bump]]]
+        );
+    }
+
+    #[test]
+    fn test_create_native_associated_token_account() {
+        test_utils::setup();
+        let owner = Pubkey::new_unique();
+        let mut lamports = [0; 3];
+        let mut data: [[u8; 0]; 3] = [[]; 3];
+        let [owner_lamports, mint_lamports, token_program_lamports] = &mut lamports;
+        let [owner_data, mint_data, token_program_data] = &mut data;
+        let owner_info = AccountInfo::new(
+            &owner,
+            true,
+            true,
+            owner_lamports,
+            owner_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        let mint_info = AccountInfo::new(
+            &spl_token::native_mint::ID,
+            false,
+            false,
+            mint_lamports,
+            mint_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let token_program = AccountInfo::new(
+            &spl_token::ID,
+            false,
+            false,
+            token_program_lamports,
+            token_program_data,
+            &solana_program::bpf_loader::ID,
+            true,
+            0,
+        );
+
+        create_native_associated_token_account(
+            &owner_info,
+            &owner_info,
+            &owner_info,
+            &mint_info,
+            &owner_info,
+            &token_program,
+            &owner_info,
+        )
+        .unwrap();
+        assert_eq!(
+            test_utils::invoked()[0].program_id,
+            spl_associated_token_account::ID
+        );
+
+        // Only the native mint of the token program is accepted.
+        assert!(create_native_associated_token_account(
+            &owner_info,
+            &owner_info,
+            &owner_info,
+            &owner_info,
+            &owner_info,
+            &token_program,
+            &owner_info,
+        )
+        .is_err());
+        assert_eq!(
+            create_native_associated_token_account(
+                &owner_info,
+                &owner_info,
+                &owner_info,
+                &mint_info,
+                &owner_info,
+                &owner_info,
+                &owner_info,
+            ),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+}
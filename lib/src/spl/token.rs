@@ -1,15 +1,27 @@
-use solana_program::{program_option::COption, pubkey::Pubkey};
+use solana_program::{program_error::ProgramError, program_option::COption, pubkey::Pubkey};
+use spl_token_2022::extension::{
+    memo_transfer::MemoTransfer, non_transferable::NonTransferableAccount,
+    transfer_fee::TransferFeeAmount, BaseStateWithExtensions, Extension, ExtensionType,
+    StateWithExtensions,
+};
 
-pub enum TokenAccount {
+use crate::trace;
+
+use super::assert_no_extensions;
+
+/// A parsed token account. Token-2022 extensions are read from the account data on demand.
+pub enum TokenAccount<'a> {
     V0(spl_token::state::Account),
     V1(spl_token_2022::state::Account),
+    V1WithExtensions(StateWithExtensions<'a, spl_token_2022::state::Account>),
 }
 
-impl TokenAccount {
+impl TokenAccount<'_> {
     pub fn mint(&self) -> Pubkey {
         match self {
             TokenAccount::V0(account) => account.mint,
             TokenAccount::V1(account) => account.mint,
+            TokenAccount::V1WithExtensions(account) => account.base.mint,
         }
     }
 
@@ -17,6 +29,7 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.owner,
             TokenAccount::V1(account) => account.owner,
+            TokenAccount::V1WithExtensions(account) => account.base.owner,
         }
     }
 
@@ -24,6 +37,7 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.amount,
             TokenAccount::V1(account) => account.amount,
+            TokenAccount::V1WithExtensions(account) => account.base.amount,
         }
     }
 
@@ -31,6 +45,7 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.delegate,
             TokenAccount::V1(account) => account.delegate,
+            TokenAccount::V1WithExtensions(account) => account.base.delegate,
         }
     }
 
@@ -38,6 +53,7 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.is_frozen(),
             TokenAccount::V1(account) => account.is_frozen(),
+            TokenAccount::V1WithExtensions(account) => account.base.is_frozen(),
         }
     }
 
@@ -45,6 +61,7 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.is_native,
             TokenAccount::V1(account) => account.is_native,
+            TokenAccount::V1WithExtensions(account) => account.base.is_native,
         }
     }
 
@@ -52,6 +69,7 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.delegated_amount,
             TokenAccount::V1(account) => account.delegated_amount,
+            TokenAccount::V1WithExtensions(account) => account.base.delegated_amount,
         }
     }
 
@@ -59,6 +77,70 @@ impl TokenAccount {
         match self {
             TokenAccount::V0(account) => account.close_authority,
             TokenAccount::V1(account) => account.close_authority,
+            TokenAccount::V1WithExtensions(account) => account.base.close_authority,
+        }
+    }
+
+    /// Returns the Token-2022 extensions on the token account, or an error if the extension data
+    /// is malformed.
+    #[track_caller]
+    pub fn extension_types(&self) -> Result<Vec<ExtensionType>, ProgramError> {
+        match self {
+            TokenAccount::V1WithExtensions(account) => account
+                .get_extension_types()
+                .map_err(|err| trace("Token account extension data is invalid", err)),
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Returns true if the token account has the given Token-2022 extension.
+    #[track_caller]
+    pub fn has_extension(&self, extension_type: ExtensionType) -> Result<bool, ProgramError> {
+        Ok(self.extension_types()?.contains(&extension_type))
+    }
+
+    /// Returns the given Token-2022 extension if the token account has it, or an error if the
+    /// extension data is malformed.
+    #[track_caller]
+    pub fn get_extension<E: Extension + bytemuck::Pod>(&self) -> Result<Option<&E>, ProgramError> {
+        match self {
+            TokenAccount::V1WithExtensions(account) if self.has_extension(E::TYPE)? => account
+                .get_extension::<E>()
+                .map(Some)
+                .map_err(|err| trace("Token account extension data is invalid", err)),
+            _ => Ok(None),
         }
     }
+
+    /// Returns true if incoming transfers must be preceded by a memo instruction.
+    #[track_caller]
+    pub fn is_memo_required(&self) -> Result<bool, ProgramError> {
+        Ok(self
+            .get_extension::<MemoTransfer>()?
+            .is_some_and(|ext| bool::from(ext.require_incoming_transfer_memos)))
+    }
+
+    #[track_caller]
+    pub fn is_non_transferable(&self) -> Result<bool, ProgramError> {
+        Ok(self.get_extension::<NonTransferableAccount>()?.is_some())
+    }
+
+    /// Returns the transfer fees withheld in the token account.
+    #[track_caller]
+    pub fn withheld_amount(&self) -> Result<u64, ProgramError> {
+        Ok(self
+            .get_extension::<TransferFeeAmount>()?
+            .map(|ext| u64::from(ext.withheld_amount))
+            .unwrap_or(0))
+    }
+
+    /// Returns an error if the token account has any of the given Token-2022 extensions.
+    #[track_caller]
+    pub fn assert_no_extensions(
+        &self,
+        extension_types: &[ExtensionType],
+    ) -> Result<&Self, ProgramError> {
+        assert_no_extensions("Token account", &self.extension_types()?, extension_types)?;
+        Ok(self)
+    }
 }
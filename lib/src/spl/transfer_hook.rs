@@ -0,0 +1,237 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use spl_transfer_hook_interface::{
+    collect_extra_account_metas_seeds, get_extra_account_metas_address,
+    instruction::{ExecuteInstruction, TransferHookInstruction},
+};
+
+use crate::{
+    account::{allocate_account, AccountInfoValidation, AccountValidation},
+    trace,
+};
+
+use super::AsSpl;
+
+/// The validated accounts and amount of a transfer hook `Execute` instruction.
+///
+/// Token-2022 invokes the transfer hook program of a mint on every transfer with the accounts
+/// `[source, mint, destination, authority, extra_account_metas, ..extra_accounts]`.
+pub struct TransferHookExecute<'a, 'info> {
+    pub source_info: &'a AccountInfo<'info>,
+    pub mint_info: &'a AccountInfo<'info>,
+    pub destination_info: &'a AccountInfo<'info>,
+    pub authority_info: &'a AccountInfo<'info>,
+    pub extra_account_metas_info: &'a AccountInfo<'info>,
+    pub extra_account_infos: &'a [AccountInfo<'info>],
+    pub amount: u64,
+}
+
+impl<'a, 'info> TransferHookExecute<'a, 'info> {
+    /// Returns true if the instruction data is a transfer hook `Execute` instruction.
+    ///
+    /// Execute instructions use an 8 byte interface discriminator, so programs should check for
+    /// them before parsing their own instruction discriminator.
+    pub fn matches(data: &[u8]) -> bool {
+        matches!(
+            TransferHookInstruction::unpack(data),
+            Ok(TransferHookInstruction::Execute { .. })
+        )
+    }
+
+    /// Parses an `Execute` instruction.
+    ///
+    /// Performs:
+    /// 1. Instruction data check
+    /// 2. Extra account metas address and owner check
+    /// 3. Source and destination token account mint checks
+    /// 4. Source transferring flag check, so the hook can only run inside a token transfer
+    /// 5. Extra account check against the stored extra account metas
+    #[track_caller]
+    pub fn try_from_accounts(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<Self, ProgramError> {
+        let amount = match TransferHookInstruction::unpack(data) {
+            Ok(TransferHookInstruction::Execute { amount }) => amount,
+            _ => {
+                return Err(trace(
+                    "Instruction is not a transfer hook execute",
+                    ProgramError::InvalidInstructionData,
+                ))
+            }
+        };
+        let [source_info, mint_info, destination_info, authority_info, extra_account_metas_info, extra_account_infos @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        extra_account_metas_info
+            .has_address(&get_extra_account_metas_address(mint_info.key, program_id))?
+            .has_owner(program_id)?;
+        let source = source_info.as_token_account()?;
+        source.assert_msg(|t| t.mint() == *mint_info.key, "source mint mismatch")?;
+        let transferring = source
+            .get_extension::<TransferHookAccount>()?
+            .is_some_and(|ext| bool::from(ext.transferring));
+        source.assert_msg(|_| transferring, "source is not transferring")?;
+        destination_info
+            .as_token_account()?
+            .assert_msg(|t| t.mint() == *mint_info.key, "destination mint mismatch")?;
+
+        ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+            accounts,
+            data,
+            program_id,
+            &extra_account_metas_info.try_borrow_data()?,
+        )
+        .map_err(|err| trace("Transfer hook extra accounts are invalid", err))?;
+
+        Ok(Self {
+            source_info,
+            mint_info,
+            destination_info,
+            authority_info,
+            extra_account_metas_info,
+            extra_account_infos,
+            amount,
+        })
+    }
+}
+
+/// Creates the extra account metas account of a mint for a transfer hook program, listing the
+/// extra accounts Token-2022 must pass to `Execute`.
+#[inline(always)]
+pub fn initialize_extra_account_meta_list<'a, 'info>(
+    extra_account_metas_info: &'a AccountInfo<'info>,
+    mint_info: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    program_id: &Pubkey,
+    extra_account_metas: &[ExtraAccountMeta],
+) -> ProgramResult {
+    allocate_account(
+        extra_account_metas_info,
+        system_program,
+        payer,
+        ExtraAccountMetaList::size_of(extra_account_metas.len())?,
+        program_id,
+        &collect_extra_account_metas_seeds(mint_info.key),
+    )?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut extra_account_metas_info.try_borrow_mut_data()?,
+        extra_account_metas,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::program_option::COption;
+    use spl_tlv_account_resolution::seeds::Seed;
+    use spl_token_2022::{
+        extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut},
+        state::{Account, AccountState},
+    };
+
+    use super::*;
+
+    fn token_account(mint: Pubkey, transferring: bool) -> Vec<u8> {
+        let extensions = [ExtensionType::TransferHookAccount];
+        let len = ExtensionType::try_calculate_account_len::<Account>(&extensions).unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data).unwrap();
+        state
+            .init_extension::<TransferHookAccount>(true)
+            .unwrap()
+            .transferring = transferring.into();
+        state.base = Account {
+            mint,
+            owner: Pubkey::new_unique(),
+            amount: 100,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_transfer_hook_execute() {
+        let program_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let metas = [ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal {
+                bytes: b"config".to_vec(),
+            }],
+            false,
+            false,
+        )
+        .unwrap()];
+        let config = Pubkey::find_program_address(&[b"config"], &program_id).0;
+
+        let keys = [
+            Pubkey::new_unique(),
+            mint,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            get_extra_account_metas_address(&mint, &program_id),
+            config,
+        ];
+        let mut metas_data = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut metas_data, &metas).unwrap();
+        let mut datas = [
+            token_account(mint, true),
+            vec![],
+            token_account(mint, false),
+            vec![],
+            metas_data,
+            vec![],
+        ];
+        let owners = [
+            spl_token_2022::ID,
+            spl_token_2022::ID,
+            spl_token_2022::ID,
+            Pubkey::default(),
+            program_id,
+            program_id,
+        ];
+        let mut lamports = [0; 6];
+        let accounts = keys
+            .iter()
+            .zip(datas.iter_mut())
+            .zip(lamports.iter_mut())
+            .zip(owners.iter())
+            .map(|(((key, data), lamports), owner)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+            })
+            .collect::<Vec<_>>();
+
+        let data = TransferHookInstruction::Execute { amount: 42 }.pack();
+        assert!(TransferHookExecute::matches(&data));
+        assert!(!TransferHookExecute::matches(&[0]));
+
+        let execute =
+            TransferHookExecute::try_from_accounts(&program_id, &accounts, &data).unwrap();
+        assert_eq!(execute.amount, 42);
+        assert_eq!(execute.extra_account_infos[0].key, &config);
+
+        // The hook must not run outside of a transfer.
+        let mut swapped = accounts.clone();
+        swapped.swap(0, 2);
+        assert!(TransferHookExecute::try_from_accounts(&program_id, &swapped, &data).is_err());
+
+        // The extra accounts must match the stored metas.
+        assert!(
+            TransferHookExecute::try_from_accounts(&program_id, &accounts[..5], &data).is_err()
+        );
+    }
+}
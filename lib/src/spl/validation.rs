@@ -3,24 +3,25 @@ use solana_program::{account_info::AccountInfo, program_error::ProgramError, pub
 use crate::{account::AccountInfoValidation, trace};
 
 use solana_program::program_pack::Pack;
+use spl_token_2022::extension::{ExtensionType, StateWithExtensions};
 
 use crate::account::AccountValidation;
 
 use super::{mint::Mint, token::TokenAccount};
 
 pub trait AsSpl {
-    fn as_mint(&self) -> Result<Mint, ProgramError>;
-    fn as_token_account(&self) -> Result<TokenAccount, ProgramError>;
+    fn as_mint(&self) -> Result<Mint<'_>, ProgramError>;
+    fn as_token_account(&self) -> Result<TokenAccount<'_>, ProgramError>;
     fn as_associated_token_account(
         &self,
         owner: &Pubkey,
         mint: &Pubkey,
-    ) -> Result<TokenAccount, ProgramError>;
+    ) -> Result<TokenAccount<'_>, ProgramError>;
 }
 
 impl AsSpl for AccountInfo<'_> {
     #[track_caller]
-    fn as_mint(&self) -> Result<Mint, ProgramError> {
+    fn as_mint(&self) -> Result<Mint<'_>, ProgramError> {
         match *self.owner {
             spl_token::ID => unsafe {
                 // Validate account data length.
@@ -42,14 +43,22 @@ impl AsSpl for AccountInfo<'_> {
             spl_token_2022::ID => unsafe {
                 // Validate account data length.
                 let data = self.try_borrow_data()?;
-                if data.len() != spl_token_2022::state::Mint::LEN {
+                if data.len() < spl_token_2022::state::Mint::LEN {
                     return Err(trace(
                         "Mint data length is invalid",
                         ProgramError::InvalidAccountData,
                     ));
                 }
 
-                // Deserialize account data.
+                // Deserialize account data, borrowing any extensions.
+                if data.len() > spl_token_2022::state::Mint::LEN {
+                    let mint = StateWithExtensions::unpack(std::slice::from_raw_parts(
+                        data.as_ptr(),
+                        data.len(),
+                    ))
+                    .map_err(|err| trace("Mint extension data is invalid", err))?;
+                    return Ok(Mint::V1WithExtensions(mint));
+                }
                 let mint = spl_token_2022::state::Mint::unpack(std::slice::from_raw_parts(
                     data.as_ptr(),
                     spl_token_2022::state::Mint::LEN,
@@ -61,7 +70,7 @@ impl AsSpl for AccountInfo<'_> {
     }
 
     #[track_caller]
-    fn as_token_account(&self) -> Result<TokenAccount, ProgramError> {
+    fn as_token_account(&self) -> Result<TokenAccount<'_>, ProgramError> {
         match *self.owner {
             spl_token::ID => unsafe {
                 // Validate account data length.
@@ -90,7 +99,15 @@ impl AsSpl for AccountInfo<'_> {
                     ));
                 }
 
-                // Deserialize account data.
+                // Deserialize account data, borrowing any extensions.
+                if data.len() > spl_token_2022::state::Account::LEN {
+                    let account = StateWithExtensions::unpack(std::slice::from_raw_parts(
+                        data.as_ptr(),
+                        data.len(),
+                    ))
+                    .map_err(|err| trace("Token account extension data is invalid", err))?;
+                    return Ok(TokenAccount::V1WithExtensions(account));
+                }
                 let account = spl_token_2022::state::Account::unpack(std::slice::from_raw_parts(
                     data.as_ptr(),
                     spl_token_2022::state::Account::LEN,
@@ -106,7 +123,7 @@ impl AsSpl for AccountInfo<'_> {
         &self,
         owner: &Pubkey,
         mint: &Pubkey,
-    ) -> Result<TokenAccount, ProgramError> {
+    ) -> Result<TokenAccount<'_>, ProgramError> {
         self.has_address(
             &spl_associated_token_account::get_associated_token_address_with_program_id(
                 owner, mint, self.owner,
@@ -116,7 +133,23 @@ impl AsSpl for AccountInfo<'_> {
     }
 }
 
-impl AccountValidation for Mint {
+/// Returns an error if any of the forbidden extensions are present.
+#[track_caller]
+pub(crate) fn assert_no_extensions(
+    kind: &str,
+    present: &[ExtensionType],
+    forbidden: &[ExtensionType],
+) -> Result<(), ProgramError> {
+    if let Some(extension_type) = forbidden.iter().find(|e| present.contains(e)) {
+        return Err(trace(
+            format!("{} has forbidden extension {:?}", kind, extension_type).as_str(),
+            ProgramError::InvalidAccountData,
+        ));
+    }
+    Ok(())
+}
+
+impl AccountValidation for Mint<'_> {
     #[track_caller]
     fn assert<F>(&self, condition: F) -> Result<&Self, ProgramError>
     where
@@ -186,7 +219,7 @@ impl AccountValidation for Mint {
     }
 }
 
-impl AccountValidation for TokenAccount {
+impl AccountValidation for TokenAccount<'_> {
     #[track_caller]
     fn assert<F>(&self, condition: F) -> Result<&Self, ProgramError>
     where
@@ -255,3 +288,249 @@ impl AccountValidation for TokenAccount {
         panic!("not implemented")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_program::program_option::COption;
+    use spl_token_2022::{
+        extension::{
+            cpi_guard::CpiGuard,
+            immutable_owner::ImmutableOwner,
+            memo_transfer::MemoTransfer,
+            non_transferable::{NonTransferable, NonTransferableAccount},
+            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            BaseStateWithExtensionsMut, StateWithExtensionsMut,
+        },
+        state::{Account as AccountState, AccountState as TokenState, Mint as MintState},
+    };
+
+    use super::*;
+
+    fn mint_with_extensions() -> Vec<u8> {
+        let extensions = [
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::NonTransferable,
+        ];
+        let len = ExtensionType::try_calculate_account_len::<MintState>(&extensions).unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<MintState>::unpack_uninitialized(&mut data).unwrap();
+        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.newer_transfer_fee.transfer_fee_basis_points = 25.into();
+        config.newer_transfer_fee.maximum_fee = 1_000.into();
+        state.init_extension::<NonTransferable>(true).unwrap();
+        state.base = MintState {
+            mint_authority: COption::None,
+            supply: 1_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_as_mint_with_extensions() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = mint_with_extensions();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+
+        let mint = info.as_mint().unwrap();
+        assert_eq!(mint.supply(), 1_000);
+        assert_eq!(mint.decimals(), 6);
+        assert_eq!(
+            mint.has_extension(ExtensionType::TransferFeeConfig),
+            Ok(true)
+        );
+        assert_eq!(mint.is_non_transferable(), Ok(true));
+        assert_eq!(
+            u16::from(
+                mint.transfer_fee_config()
+                    .unwrap()
+                    .unwrap()
+                    .newer_transfer_fee
+                    .transfer_fee_basis_points
+            ),
+            25
+        );
+        assert_eq!(mint.permanent_delegate(), Ok(None));
+        assert!(mint
+            .assert_no_extensions(&[ExtensionType::PermanentDelegate])
+            .is_ok());
+        assert_eq!(
+            mint.assert_no_extensions(&[ExtensionType::NonTransferable])
+                .err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_mint_transfer_fee() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = mint_with_extensions();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+
+        let mint = info.as_mint().unwrap();
+        assert_eq!(mint.transfer_fee_for_epoch(10_000, 0), Ok(25));
+        assert_eq!(mint.transfer_fee_for_epoch(10_001, 0), Ok(26));
+        assert_eq!(mint.transfer_fee_for_epoch(u64::MAX, 0), Ok(1_000));
+        assert_eq!(mint.amount_after_fee_for_epoch(10_000, 0), Ok(9_975));
+        assert_eq!(mint.amount_before_fee_for_epoch(9_975, 0), Ok(10_000));
+    }
+
+    fn token_account_with_extensions(mint: Pubkey, owner: Pubkey) -> Vec<u8> {
+        let extensions = [
+            ExtensionType::MemoTransfer,
+            ExtensionType::TransferFeeAmount,
+            ExtensionType::NonTransferableAccount,
+            ExtensionType::ImmutableOwner,
+        ];
+        let len = ExtensionType::try_calculate_account_len::<AccountState>(&extensions).unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<AccountState>::unpack_uninitialized(&mut data).unwrap();
+        let memo = state.init_extension::<MemoTransfer>(true).unwrap();
+        memo.require_incoming_transfer_memos = true.into();
+        let fee = state.init_extension::<TransferFeeAmount>(true).unwrap();
+        fee.withheld_amount = 7.into();
+        state
+            .init_extension::<NonTransferableAccount>(true)
+            .unwrap();
+        state.init_extension::<ImmutableOwner>(true).unwrap();
+        state.base = AccountState {
+            mint,
+            owner,
+            amount: 500,
+            state: TokenState::Initialized,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_as_token_account_with_extensions() {
+        let key = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = token_account_with_extensions(mint, owner);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+
+        let account = info.as_token_account().unwrap();
+        assert_eq!(account.mint(), mint);
+        assert_eq!(account.owner(), owner);
+        assert_eq!(account.amount(), 500);
+        assert_eq!(account.is_memo_required(), Ok(true));
+        assert_eq!(account.withheld_amount(), Ok(7));
+        assert_eq!(account.is_non_transferable(), Ok(true));
+        assert_eq!(
+            account.has_extension(ExtensionType::ImmutableOwner),
+            Ok(true)
+        );
+        assert_eq!(account.has_extension(ExtensionType::CpiGuard), Ok(false));
+        assert_eq!(account.get_extension::<CpiGuard>(), Ok(None));
+        assert!(account
+            .assert_no_extensions(&[ExtensionType::CpiGuard])
+            .is_ok());
+        assert_eq!(
+            account
+                .assert_no_extensions(&[ExtensionType::NonTransferableAccount])
+                .err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_token_account_without_extensions() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0; AccountState::LEN];
+        AccountState {
+            state: TokenState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+
+        let account = info.as_token_account().unwrap();
+        assert_eq!(account.extension_types(), Ok(vec![]));
+        assert_eq!(account.is_memo_required(), Ok(false));
+        assert_eq!(account.withheld_amount(), Ok(0));
+        assert_eq!(account.is_non_transferable(), Ok(false));
+    }
+
+    #[test]
+    fn test_malformed_extensions() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = token_account_with_extensions(Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Point the length of the first extension past the end of the account.
+        let tlv = AccountState::LEN + 1;
+        data[tlv + 2..tlv + 4].copy_from_slice(&u16::MAX.to_le_bytes());
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+
+        // Malformed extension data is an error rather than an empty list of extensions.
+        let account = info.as_token_account().unwrap();
+        assert!(account.extension_types().is_err());
+        assert!(account
+            .assert_no_extensions(&[ExtensionType::CpiGuard])
+            .is_err());
+
+        // Extensions can't be read from malformed data, so they aren't reported as absent.
+        assert!(account.get_extension::<MemoTransfer>().is_err());
+        assert!(account.get_extension::<CpiGuard>().is_err());
+        assert!(account.is_memo_required().is_err());
+    }
+}
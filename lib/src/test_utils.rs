@@ -0,0 +1,85 @@
+use std::{cell::RefCell, sync::Once};
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    rent::Rent,
+    system_program,
+};
+
+/// The seeds of each PDA signing an invoked instruction.
+type SignerSeeds = Vec<Vec<Vec<u8>>>;
+
+thread_local! {
+    static INVOKED: RefCell<Vec<Instruction>> = const { RefCell::new(Vec::new()) };
+    static SIGNER_SEEDS: RefCell<Vec<SignerSeeds>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Syscall stubs for native tests of code that reads the rent sysvar or invokes other programs.
+///
+/// Invoked instructions and their signer seeds are recorded per test thread. System transfers are applied to the
+/// accounts, every other instruction only succeeds.
+struct TestSyscallStubs;
+
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id.eq(&system_program::ID) && instruction.data[..4] == [2, 0, 0, 0] {
+            let amount = u64::from_le_bytes(instruction.data[4..12].try_into().unwrap());
+            let find = |index: usize| {
+                account_infos
+                    .iter()
+                    .find(|info| info.key.eq(&instruction.accounts[index].pubkey))
+                    .ok_or(ProgramError::NotEnoughAccountKeys)
+            };
+            let (from, to) = (find(0)?, find(1)?);
+            **from.try_borrow_mut_lamports()? = from
+                .lamports()
+                .checked_sub(amount)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            **to.try_borrow_mut_lamports()? += amount;
+        }
+        INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+        SIGNER_SEEDS.with(|signer_seeds| {
+            signer_seeds.borrow_mut().push(
+                signers_seeds
+                    .iter()
+                    .map(|seeds| seeds.iter().map(|seed| seed.to_vec()).collect())
+                    .collect(),
+            )
+        });
+        Ok(())
+    }
+}
+
+/// Installs the test syscall stubs and clears the instructions invoked on this thread.
+pub(crate) fn setup() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(TestSyscallStubs));
+    });
+    INVOKED.with(|invoked| invoked.borrow_mut().clear());
+    SIGNER_SEEDS.with(|signer_seeds| signer_seeds.borrow_mut().clear());
+}
+
+/// Returns the instructions invoked on this thread since `setup`.
+pub(crate) fn invoked() -> Vec<Instruction> {
+    INVOKED.with(|invoked| invoked.borrow().clone())
+}
+
+/// Returns the signer seeds of each instruction invoked on this thread since `setup`.
+pub(crate) fn signer_seeds() -> Vec<SignerSeeds> {
+    SIGNER_SEEDS.with(|signer_seeds| signer_seeds.borrow().clone())
+}
@@ -1,6 +1,7 @@
+use num_enum::TryFromPrimitive;
 use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
-/// Parses an instruction from the instruction data.
+/// Parses an instruction with a 1 byte discriminator from the instruction data.
 pub fn parse_instruction<'a, T: std::convert::TryFrom<u8>>(
     api_id: &'a Pubkey,
     program_id: &'a Pubkey,
@@ -23,6 +24,65 @@ pub fn parse_instruction<'a, T: std::convert::TryFrom<u8>>(
     Ok((ix, data))
 }
 
+/// Parses an instruction from the instruction data.
+///
+/// The width of the instruction discriminator is given by the `repr` of the instruction enum,
+/// e.g. a `#[repr(u16)]` enum reads a 2 byte little-endian discriminator.
+pub fn parse_instruction_wide<'a, T>(
+    api_id: &'a Pubkey,
+    program_id: &'a Pubkey,
+    data: &'a [u8],
+) -> Result<(T, &'a [u8]), ProgramError>
+where
+    T: TryFromPrimitive,
+    T::Primitive: DiscriminatorPrimitive,
+{
+    // Validate the program id is valid.
+    if program_id.ne(api_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Parse data for instruction discriminator.
+    if data.len() < T::Primitive::SIZE {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (tag, data) = data.split_at(T::Primitive::SIZE);
+
+    // Get instruction for discriminator.
+    let ix = T::try_from_primitive(T::Primitive::from_le_slice(tag))
+        .or(Err(ProgramError::InvalidInstructionData))?;
+
+    // Return
+    Ok((ix, data))
+}
+
+/// An unsigned integer that can back an instruction discriminator enum.
+pub trait DiscriminatorPrimitive: Sized {
+    /// The width of the discriminator in bytes.
+    const SIZE: usize;
+
+    /// Reads the discriminator from exactly `SIZE` little-endian bytes.
+    fn from_le_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_discriminator_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl DiscriminatorPrimitive for $ty {
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                fn from_le_slice(bytes: &[u8]) -> Self {
+                    let mut buf = [0u8; std::mem::size_of::<$ty>()];
+                    buf.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_discriminator_primitive!(u8, u16, u32, u64);
+
 /// Converts a string into a fixed-size byte array of length N.
 ///
 /// # Arguments
@@ -31,6 +91,7 @@ pub fn parse_instruction<'a, T: std::convert::TryFrom<u8>>(
 /// # Returns
 /// * `Ok([u8; N])` - A fixed-size byte array containing the string data, zero-padded if needed
 /// * `Err(ProgramError)` - Returns ERROR_STRING_TOO_LONG if input string is longer than N bytes
+#[deprecated(note = "Use `PodString::try_from_str` instead")]
 pub fn string_to_bytes<const N: usize>(s: &str) -> Result<[u8; N], ProgramError> {
     let mut bytes = [0; N];
     let s_bytes = s.as_bytes();
@@ -53,6 +114,7 @@ pub fn string_to_bytes<const N: usize>(s: &str) -> Result<[u8; N], ProgramError>
 /// # Returns
 /// * `Ok(String)` - The converted string, truncated at the first null byte if present
 /// * `Err(ProgramError)` - Returns ERROR_INVALID_UTF8 if the bytes are not valid UTF-8
+#[deprecated(note = "Use `PodString::as_str` or `PodString::to_string_lossy` instead")]
 pub fn bytes_to_string<const N: usize>(bytes: &[u8; N]) -> Result<String, ProgramError> {
     // Find the actual length by looking for the first zero or taking full length
     let actual_len = bytes.iter().position(|&b| b == 0).unwrap_or(N);
@@ -67,6 +129,7 @@ pub const ERROR_STRING_TOO_LONG: u32 = 1;
 pub const ERROR_INVALID_UTF8: u32 = 2;
 
 #[test]
+#[allow(deprecated)]
 fn test_string_to_bytes() {
     // Test successful conversion
     let result = string_to_bytes::<5>("hello");
@@ -88,6 +151,7 @@ fn test_string_to_bytes() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_bytes_to_string() {
     // Test successful conversion
     let bytes = *b"hello";
@@ -102,3 +166,51 @@ fn test_bytes_to_string() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), "hi");
 }
+
+#[test]
+fn test_parse_instruction() {
+    #[repr(u8)]
+    #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+    enum MyInstruction {
+        Initialize = 0,
+        Add = 1,
+    }
+
+    let id = Pubkey::new_unique();
+    let (ix, data) = parse_instruction::<MyInstruction>(&id, &id, &[1, 9]).unwrap();
+    assert_eq!(ix, MyInstruction::Add);
+    assert_eq!(data, &[9]);
+
+    // Test wrong program id
+    let other = Pubkey::new_unique();
+    let result = parse_instruction::<MyInstruction>(&id, &other, &[1]);
+    assert_eq!(result.unwrap_err(), ProgramError::IncorrectProgramId);
+
+    // Test empty data
+    let result = parse_instruction::<MyInstruction>(&id, &id, &[]);
+    assert_eq!(result.unwrap_err(), ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn test_parse_instruction_wide() {
+    #[repr(u16)]
+    #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
+    enum WideInstruction {
+        Initialize = 0,
+        Add = 300,
+    }
+
+    let id = Pubkey::new_unique();
+    let data = [44, 1, 9];
+    let (ix, data) = parse_instruction_wide::<WideInstruction>(&id, &id, &data).unwrap();
+    assert_eq!(ix, WideInstruction::Add);
+    assert_eq!(data, &[9]);
+
+    // Test discriminator shorter than the enum width
+    let result = parse_instruction_wide::<WideInstruction>(&id, &id, &[0]);
+    assert_eq!(result.unwrap_err(), ProgramError::InvalidInstructionData);
+
+    // Test unknown discriminator
+    let result = parse_instruction_wide::<WideInstruction>(&id, &id, &[1, 0]);
+    assert_eq!(result.unwrap_err(), ProgramError::InvalidInstructionData);
+}